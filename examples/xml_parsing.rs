@@ -7,7 +7,7 @@ fn main() {
         debugger::wait_until_attached(None).expect("Could't attach the debugger");
     }
 
-    let xml_text: SvnList = serde_xml_rs::from_str(LIST_XML.trim()).unwrap();
+    let xml_text: SvnList = quick_xml::de::from_str(LIST_XML.trim()).unwrap();
     println!("{:?}", xml_text);
 }
 
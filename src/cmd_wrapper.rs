@@ -1,6 +1,6 @@
 //! this module will implement all svn cmd wrapper
 
-use crate::errors::SvnError;
+use crate::{errors::SvnError, xml_stream::StdoutReader};
 use log::trace;
 use managed_command::Command as ManagedCommand;
 use rr_common_utils::{Future, JobDesc, ThreadPool};
@@ -21,6 +21,39 @@ impl SvnWrapper {
     }
 }
 
+/// output of a finished svn command, whatever its exit status
+#[derive(Debug)]
+pub(crate) struct CmdOutput {
+    /// exit status is 0
+    pub(crate) success: bool,
+    pub(crate) stdout: String,
+    /// warnings and errors, `svn: warning: W...` or `svn: E...` lines
+    pub(crate) stderr: String,
+}
+
+impl CmdOutput {
+    /// stdout, or the error `common_cmd_runner` returns when anything was printed to stderr
+    pub(crate) fn into_stdout(self) -> Result<String, SvnError> {
+        if self.stderr.is_empty() {
+            Ok(self.stdout)
+        } else {
+            Err(stderr_error(self.stderr))
+        }
+    }
+}
+
+/// error for the stderr of a svn command, working copy format errors are told apart so the
+/// caller can ask for `svn upgrade`
+pub(crate) fn stderr_error(stderr: String) -> SvnError {
+    if stderr.contains("svn: E155036:") {
+        SvnError::WcNeedsUpgrade(stderr)
+    } else if stderr.contains("svn: E155021:") {
+        SvnError::WcTooNew(stderr)
+    } else {
+        SvnError::Other(format!("some error while running svn command: {stderr:?}"))
+    }
+}
+
 /// This wraps in rr_common_utils::Future<String> for stdout
 #[derive(Debug)]
 pub struct StdoutFuture(pub Future<String>);
@@ -47,19 +80,36 @@ impl SvnWrapper {
                         SvnError::FromUtf8Error(String::from_utf8_lossy(e.as_bytes()).into_owned())
                     })
                 } else {
-                    Err(SvnError::Other(format!(
-                        "some error while running svn command: {:?}",
-                        match String::from_utf8(o.stderr) {
-                            Ok(o) => o,
-                            Err(e) => e.to_string(),
-                        }
-                    )))
+                    Err(stderr_error(match String::from_utf8(o.stderr) {
+                        Ok(o) => o,
+                        Err(e) => e.to_string(),
+                    }))
                 }
             }
             Err(e) => Err(SvnError::MissingSvnCli(e)),
         }
     }
 
+    /// like `common_cmd_runner` but warnings and errors are returned to the caller, for the
+    /// commands reporting failures per path
+    pub(crate) fn common_cmd_runner_output(&self, args: &[&str]) -> Result<CmdOutput, SvnError> {
+        trace!("command args: {:?}", args);
+        let o = Command::new(&self.cmd)
+            .args(args)
+            .creation_flags(Self::CREATE_NO_WINDOW)
+            .output()
+            .map_err(SvnError::MissingSvnCli)?;
+        let stdout = String::from_utf8(o.stdout).map_err(|e| {
+            trace!("invalid utf8 output of svn cmd '{:?} {args:?}'", self.cmd,);
+            SvnError::FromUtf8Error(String::from_utf8_lossy(e.as_bytes()).into_owned())
+        })?;
+        Ok(CmdOutput {
+            success: o.status.success(),
+            stdout,
+            stderr: String::from_utf8_lossy(&o.stderr).into_owned(),
+        })
+    }
+
     #[allow(dead_code)]
     pub(crate) fn common_cmd_runner_cancellable(
         &self,
@@ -100,4 +150,32 @@ impl SvnWrapper {
         ));
         Ok((stdout_future, stderr_future))
     }
+
+    pub(crate) fn common_cmd_runner_streaming(
+        &self,
+        args: &[&str],
+        canceller: Canceller,
+    ) -> Result<(StdoutReader, StderrFuture), SvnError> {
+        trace!("command args: {:?}", args);
+        let mut cmd = Command::new(&self.cmd);
+        cmd.args(args);
+        cmd.creation_flags(Self::CREATE_NO_WINDOW);
+        let mut cmd: ManagedCommand = cmd.into();
+        let (_stdin, stdout, stderr) = cmd.run(canceller.clone_as(format!("run: {args:?}")))?;
+        let stderr_future = StderrFuture(ThreadPool::global().run_async(
+            move || {
+                let mut out = String::new();
+                while let Ok(stderr_str) = stderr.recv() {
+                    out.push_str(stderr_str.as_str());
+                }
+                out
+            },
+            JobDesc::create(
+                "common_cmd_runner_streaming".to_owned(),
+                format!("capturing the stderr of svn cmd '{args:?}'"),
+            ),
+        ));
+        let stdout_reader = StdoutReader::new(Box::new(move || stdout.recv().ok()));
+        Ok((stdout_reader, stderr_future))
+    }
 }
@@ -20,7 +20,11 @@ pub enum SvnError {
 
     /// invalid UTF8 output
     #[error(transparent)]
-    Deserializer(#[from] serde_xml_rs::Error),
+    Deserializer(#[from] quick_xml::DeError),
+
+    /// malformed XML output
+    #[error(transparent)]
+    XmlReader(#[from] quick_xml::Error),
 
     /// requested path doesn't exist
     #[error("requested path doesn't exist")]
@@ -42,6 +46,22 @@ pub enum SvnError {
     #[error(transparent)]
     OneshotRecvError(#[from] oneshot::RecvError),
 
+    /// malformed `svn:externals` definition
+    #[error("invalid svn:externals definition: `{0}`")]
+    InvalidExternal(String),
+
+    /// malformed `svn:mergeinfo` line
+    #[error("invalid svn:mergeinfo: `{0}`")]
+    InvalidMergeinfo(String),
+
+    /// working copy format is older than the client handles, E155036, run `svn upgrade`
+    #[error("working copy needs `svn upgrade`: `{0}`")]
+    WcNeedsUpgrade(String),
+
+    /// working copy was written by a newer svn client, E155021
+    #[error("svn client is too old for the working copy: `{0}`")]
+    WcTooNew(String),
+
     /// other error
     #[error("other error: `{0}`")]
     Other(String),
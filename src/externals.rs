@@ -0,0 +1,556 @@
+//! parser and writer of `svn:externals` definitions
+
+use crate::{errors::SvnError, sub_commands::info::InfoEntry, types::RevisionType};
+use itertools::{EitherOrBoth, Itertools};
+use std::fmt::{self, Display, Write};
+use url::Url;
+
+/// syntax of a definition
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExternalFormat {
+    /// svn 1.4 and older, `DIR [-r REV] URL`, the url is absolute and `-r` is also its peg
+    Old,
+    /// svn 1.5 and newer, `[-r REV] URL[@PEG] DIR`
+    New,
+}
+
+/// one definition of `svn:externals`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExternalDefinition {
+    /// url as written, absolute or relative with `../`, `^/`, `//` or `/`
+    pub url: String,
+    /// local path of the external, relative to the directory owning the property
+    pub target: String,
+    /// operative revision, `-r REV`
+    pub revision: Option<RevisionType>,
+    /// peg revision, `URL@PEG`
+    pub peg_revision: Option<RevisionType>,
+    /// syntax the definition is written in
+    pub format: ExternalFormat,
+}
+
+/// line of `svn:externals`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ExternalsLine {
+    /// external definition
+    Definition(ExternalDefinition),
+    /// comment or blank line, kept as is
+    Other(String),
+}
+
+/// revision `SvnCmd::externals_pin` moves the externals to
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PinMode {
+    /// revision checked out in the working copy
+    CheckedOut,
+    /// HEAD revision of the external url
+    Head,
+    /// no revision, the externals follow HEAD again
+    Float,
+}
+
+/// new value of `svn:externals` on one directory
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExternalsChange {
+    /// directory owning the property
+    pub path: String,
+    /// value before the change
+    pub old: String,
+    /// value after the change
+    pub new: String,
+}
+
+impl ExternalsChange {
+    /// changed lines, '-' for the old and '+' for the new line
+    pub fn diff(&self) -> String {
+        let mut diff = format!("--- {0}\n+++ {0}\n", self.path);
+        for lines in self.old.lines().zip_longest(self.new.lines()) {
+            let _ = match lines {
+                EitherOrBoth::Both(old, new) if old == new => Ok(()),
+                EitherOrBoth::Both(old, new) => writeln!(diff, "-{old}\n+{new}"),
+                EitherOrBoth::Left(old) => writeln!(diff, "-{old}"),
+                EitherOrBoth::Right(new) => writeln!(diff, "+{new}"),
+            };
+        }
+        diff
+    }
+}
+
+/// value of `svn:externals`, comments and blank lines are kept so it can be written back
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SvnExternals {
+    /// every line of the value
+    pub lines: Vec<ExternalsLine>,
+    // line as written and definition as parsed, by line, unchanged definitions are written
+    // back with their original spelling
+    original: Vec<Option<(String, ExternalDefinition)>>,
+    trailing_newline: bool,
+}
+
+impl SvnExternals {
+    /// parse the property value, fails on the first invalid definition like svn does
+    pub fn parse(value: &str) -> Result<Self, SvnError> {
+        let mut lines = Vec::new();
+        let mut original = Vec::new();
+        for line in value.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                lines.push(ExternalsLine::Other(line.to_owned()));
+                original.push(None);
+            } else {
+                let def = ExternalDefinition::parse(trimmed)?;
+                original.push(Some((line.to_owned(), def.clone())));
+                lines.push(ExternalsLine::Definition(def));
+            }
+        }
+        Ok(Self {
+            lines,
+            original,
+            trailing_newline: value.ends_with('\n'),
+        })
+    }
+
+    /// every definition, in property order
+    pub fn definitions(&self) -> impl Iterator<Item = &ExternalDefinition> {
+        self.lines.iter().filter_map(|line| match line {
+            ExternalsLine::Definition(def) => Some(def),
+            ExternalsLine::Other(_) => None,
+        })
+    }
+
+    /// every definition, for editing in place
+    pub fn definitions_mut(&mut self) -> impl Iterator<Item = &mut ExternalDefinition> {
+        self.lines.iter_mut().filter_map(|line| match line {
+            ExternalsLine::Definition(def) => Some(def),
+            ExternalsLine::Other(_) => None,
+        })
+    }
+
+    /// pin every definition without a revision number, `revision_of` returns the revision
+    /// to pin to, the new format gets a peg revision and the old one `-r`
+    pub fn pin(
+        &mut self,
+        mut revision_of: impl FnMut(&ExternalDefinition) -> Result<u64, SvnError>,
+    ) -> Result<(), SvnError> {
+        for def in self.definitions_mut().filter(|d| !d.is_pinned()) {
+            let revision = RevisionType::Revision(revision_of(def)?);
+            match def.format {
+                ExternalFormat::New => {
+                    def.revision = None;
+                    def.peg_revision = Some(revision);
+                }
+                ExternalFormat::Old => def.revision = Some(revision),
+            }
+        }
+        Ok(())
+    }
+
+    /// drop every revision, so the externals follow HEAD
+    pub fn unpin(&mut self) {
+        for def in self.definitions_mut() {
+            def.revision = None;
+            def.peg_revision = None;
+        }
+    }
+}
+
+impl Display for SvnExternals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match (line, self.original.get(i)) {
+                (ExternalsLine::Definition(def), Some(Some((text, parsed)))) if def == parsed => {
+                    write!(f, "{text}")?
+                }
+                (ExternalsLine::Definition(def), _) => write!(f, "{def}")?,
+                (ExternalsLine::Other(other), _) => write!(f, "{other}")?,
+            }
+        }
+        if self.trailing_newline {
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl ExternalDefinition {
+    /// parse one definition, following the rules of `svn_wc_parse_externals_description`
+    pub fn parse(line: &str) -> Result<Self, SvnError> {
+        let invalid = |reason: &str| SvnError::InvalidExternal(format!("{reason}: '{line}'"));
+        let mut tokens = tokenize(line).map_err(invalid)?;
+        if !(2..=4).contains(&tokens.len()) {
+            return Err(invalid("expected 2 to 4 fields"));
+        }
+
+        // `-r REV` or `-rREV`, either before or after the first field
+        let rev_index = tokens.iter().position(|t| t.starts_with("-r"));
+        let mut revision = None;
+        if let Some(i) = rev_index {
+            let (rev, len) = match &tokens[i][2..] {
+                "" => (tokens.get(i + 1).cloned().unwrap_or_default(), 2),
+                rev => (rev.to_owned(), 1),
+            };
+            revision = Some(RevisionType::parse(&rev).ok_or_else(|| invalid("invalid revision"))?);
+            tokens.drain(i..(i + len).min(tokens.len()));
+        }
+        if tokens.len() != 2 || rev_index.is_some_and(|i| i > 1) {
+            return Err(invalid("expected a url and a local path"));
+        }
+        let token1 = tokens.pop().unwrap_or_default();
+        let token0 = tokens.pop().unwrap_or_default();
+        let token0_is_url = is_absolute_url(&token0);
+        let token1_is_url = is_absolute_url(&token1);
+        if token0_is_url && token1_is_url {
+            return Err(invalid("two absolute urls"));
+        }
+        if rev_index == Some(0) && token1_is_url {
+            return Err(invalid("the local path can't be an url"));
+        }
+
+        let def =
+            if rev_index == Some(0) || (rev_index.is_none() && (token0_is_url || !token1_is_url)) {
+                let (url, peg_revision) =
+                    split_peg(&token0).ok_or_else(|| invalid("invalid peg revision"))?;
+                Self {
+                    url,
+                    target: token1,
+                    revision,
+                    peg_revision,
+                    format: ExternalFormat::New,
+                }
+            } else if token1_is_url {
+                Self {
+                    url: token1,
+                    target: token0,
+                    revision,
+                    peg_revision: None,
+                    format: ExternalFormat::Old,
+                }
+            } else {
+                return Err(invalid("the url must be absolute"));
+            };
+        if def.target.is_empty()
+            || def.target.starts_with('/')
+            || def.target.split('/').any(|c| c == "..")
+        {
+            return Err(invalid(
+                "the local path must be relative and below the owning dir",
+            ));
+        }
+        Ok(def)
+    }
+
+    /// url isn't absolute
+    pub fn is_relative(&self) -> bool {
+        !is_absolute_url(&self.url)
+    }
+
+    /// an explicit revision number is set, so updates don't move the external
+    pub fn is_pinned(&self) -> bool {
+        matches!(self.revision, Some(RevisionType::Revision(_)))
+            || matches!(self.peg_revision, Some(RevisionType::Revision(_)))
+    }
+
+    /// absolute url, `parent_url` is the url of the directory owning the property
+    pub fn resolve_url(&self, parent_url: &str, repos_root_url: &str) -> Result<String, SvnError> {
+        let invalid =
+            |reason: String| SvnError::InvalidExternal(format!("{reason}: '{}'", self.url));
+        let dir = |url: &str| {
+            Url::parse(&format!("{}/", url.trim_end_matches('/')))
+                .map_err(|e| invalid(format!("invalid base url '{url}', e: {e:?}")))
+        };
+        let resolved = if is_absolute_url(&self.url) {
+            Url::parse(&self.url).map_err(|e| invalid(format!("{e:?}")))?
+        } else if let Some(rest) = self.url.strip_prefix("^/") {
+            dir(repos_root_url)?
+                .join(rest)
+                .map_err(|e| invalid(format!("{e:?}")))?
+        } else if self.url.starts_with("../") || self.url.starts_with('/') {
+            dir(parent_url)?
+                .join(&self.url)
+                .map_err(|e| invalid(format!("{e:?}")))?
+        } else {
+            return Err(invalid("unrecognized relative url".to_owned()));
+        };
+        Ok(resolved.to_string())
+    }
+
+    /// absolute url, `owner` is the info of the directory owning the property
+    pub fn resolved_url(&self, owner: &InfoEntry) -> Result<String, SvnError> {
+        self.resolve_url(&owner.url, &owner.repository.root)
+    }
+}
+
+impl Display for ExternalDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let revision = self.revision.map(|r| format!("-r{r} ")).unwrap_or_default();
+        match self.format {
+            ExternalFormat::Old => {
+                write!(f, "{} {revision}{}", quote(&self.target), quote(&self.url))
+            }
+            ExternalFormat::New => {
+                // an '@' in the last component would be read back as a peg revision
+                let last = &self.url[self.url.rfind('/').map_or(0, |i| i + 1)..];
+                let peg = match self.peg_revision {
+                    Some(peg) => format!("@{peg}"),
+                    None if last.contains('@') => "@".to_owned(),
+                    None => String::new(),
+                };
+                write!(
+                    f,
+                    "{revision}{}{peg} {}",
+                    quote(&self.url),
+                    quote(&self.target)
+                )
+            }
+        }
+    }
+}
+
+fn is_absolute_url(url: &str) -> bool {
+    url.split_once("://").is_some_and(|(scheme, _)| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    })
+}
+
+// split `URL@PEG`, only an '@' in the last path component starts a peg revision and a
+// trailing '@' escapes an '@' which is part of the url
+fn split_peg(token: &str) -> Option<(String, Option<RevisionType>)> {
+    let last = token.rfind('/').map_or(0, |i| i + 1);
+    match token[last..].rfind('@') {
+        Some(at) => {
+            let (url, peg) = token.split_at(last + at);
+            match &peg[1..] {
+                "" => Some((url.to_owned(), None)),
+                peg => RevisionType::parse(peg).map(|peg| (url.to_owned(), Some(peg))),
+            }
+        }
+        None => Some((token.to_owned(), None)),
+    }
+}
+
+// split on whitespace like `apr_tokenize_to_argv`, honouring quotes and backslash escapes
+fn tokenize(line: &str) -> Result<Vec<String>, &'static str> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(tokens);
+        }
+        let mut token = String::new();
+        let mut quote: Option<char> = None;
+        while let Some(c) = chars.next() {
+            match (c, quote) {
+                ('\\', _) => token.push(chars.next().ok_or("trailing backslash")?),
+                (c, Some(q)) if c == q => quote = None,
+                ('"' | '\'', None) => quote = Some(c),
+                (c, None) if c.is_whitespace() => break,
+                (c, _) => token.push(c),
+            }
+        }
+        if quote.is_some() {
+            return Err("unbalanced quotes");
+        }
+        tokens.push(token);
+    }
+}
+
+fn quote(token: &str) -> String {
+    if token
+        .chars()
+        .any(|c| c.is_whitespace() || "\"'\\".contains(c))
+    {
+        format!("\"{}\"", token.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        token.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_formats() {
+        let externals = SvnExternals::parse(EXTERNALS).unwrap();
+        let defs: Vec<_> = externals.definitions().collect();
+        assert_eq!(defs.len(), 8);
+
+        assert_eq!(defs[0].format, ExternalFormat::New);
+        assert_eq!(defs[0].target, "FuDaiLianLianCommon");
+        assert!(!defs[0].is_pinned());
+
+        assert_eq!(defs[1].revision, Some(RevisionType::Revision(310568)));
+        assert_eq!(defs[1].url, "^/GDK_games/BLS/NitroCommon/trunk/source");
+        assert!(defs[1].is_pinned());
+
+        assert_eq!(defs[2].peg_revision, Some(RevisionType::Revision(305000)));
+        assert_eq!(defs[2].revision, Some(RevisionType::Head));
+
+        assert_eq!(defs[3].format, ExternalFormat::Old);
+        assert_eq!(defs[3].target, "third_party/zlib");
+        assert_eq!(defs[3].revision, Some(RevisionType::Revision(21)));
+        assert!(!defs[3].is_relative());
+
+        assert_eq!(defs[4].format, ExternalFormat::Old);
+        assert_eq!(defs[4].revision, None);
+
+        assert_eq!(defs[5].target, "Shared Assets");
+        assert_eq!(defs[6].target, "Shared Fonts");
+        assert_eq!(defs[7].url, "/svn/tools/trunk@2");
+        assert_eq!(defs[7].peg_revision, None);
+    }
+
+    #[test]
+    fn write_back() {
+        let mut externals = SvnExternals::parse(EXTERNALS).unwrap();
+        assert_eq!(externals.to_string(), EXTERNALS);
+
+        // edited definitions are written in the normalized form, the others as they were
+        for def in externals.definitions_mut() {
+            if def.target == "NitroCommon" {
+                def.revision = Some(RevisionType::Revision(310570));
+            }
+        }
+        assert_eq!(
+            externals.to_string(),
+            r#"# shared libraries
+^/GDK_games/BLS/FuDaiLianLianCommon/tags/Release.009/source FuDaiLianLianCommon
+-r310570 ^/GDK_games/BLS/NitroCommon/trunk/source NitroCommon
+-rHEAD ../../NitroParticles/trunk/source@305000 NitroParticles
+
+third_party/zlib -r21 https://svn.ali.global/vendor/zlib/trunk
+third_party/png https://svn.ali.global/vendor/png/trunk
+//svn.ali.global/GDK_games/assets/trunk "Shared Assets"
+/GDK_games/fonts/trunk Shared\ Fonts
+/svn/tools/trunk@2@ tools
+"#
+        );
+        let reparsed = SvnExternals::parse(&externals.to_string()).unwrap();
+        assert!(reparsed.definitions().eq(externals.definitions()));
+
+        // the normalized form reads back the same
+        let normalized: Vec<_> = externals.definitions().map(|d| d.to_string()).collect();
+        assert_eq!(normalized[6], "/GDK_games/fonts/trunk \"Shared Fonts\"");
+        for (line, def) in normalized.iter().zip(externals.definitions()) {
+            assert_eq!(&ExternalDefinition::parse(line).unwrap(), def);
+        }
+    }
+
+    #[test]
+    fn resolve_relative_urls() {
+        let externals = SvnExternals::parse(EXTERNALS).unwrap();
+        let parent = "https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/lib";
+        let root = "https://svn.ali.global/GDK_games";
+        let urls: Vec<_> = externals
+            .definitions()
+            .map(|d| d.resolve_url(parent, root).unwrap())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://svn.ali.global/GDK_games/GDK_games/BLS/FuDaiLianLianCommon/tags/Release.009/source",
+                "https://svn.ali.global/GDK_games/GDK_games/BLS/NitroCommon/trunk/source",
+                "https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/NitroParticles/trunk/source",
+                "https://svn.ali.global/vendor/zlib/trunk",
+                "https://svn.ali.global/vendor/png/trunk",
+                "https://svn.ali.global/GDK_games/assets/trunk",
+                "https://svn.ali.global/GDK_games/fonts/trunk",
+                "https://svn.ali.global/svn/tools/trunk@2",
+            ]
+        );
+    }
+
+    #[test]
+    fn pin_and_float() {
+        let mut externals = SvnExternals::parse(EXTERNALS).unwrap();
+        let original = externals.clone();
+        externals
+            .pin(|def| Ok(if def.target == "tools" { 7 } else { 400100 }))
+            .unwrap();
+        assert!(externals.definitions().all(ExternalDefinition::is_pinned));
+        assert_ne!(externals, original);
+        // against the raw value, the lines already pinned don't show up
+        let change = ExternalsChange {
+            path: "source/lib".to_owned(),
+            old: EXTERNALS.to_owned(),
+            new: externals.to_string(),
+        };
+        assert_eq!(
+            change.diff(),
+            r#"--- source/lib
++++ source/lib
+-^/GDK_games/BLS/FuDaiLianLianCommon/tags/Release.009/source FuDaiLianLianCommon
++^/GDK_games/BLS/FuDaiLianLianCommon/tags/Release.009/source@400100 FuDaiLianLianCommon
+-third_party/png https://svn.ali.global/vendor/png/trunk
++third_party/png -r400100 https://svn.ali.global/vendor/png/trunk
+-//svn.ali.global/GDK_games/assets/trunk "Shared Assets"
++//svn.ali.global/GDK_games/assets/trunk@400100 "Shared Assets"
+-/GDK_games/fonts/trunk Shared\ Fonts
++/GDK_games/fonts/trunk@400100 "Shared Fonts"
+-/svn/tools/trunk@2@ tools
++/svn/tools/trunk@2@7 tools
+"#
+        );
+
+        // pinning again changes nothing
+        let pinned = externals.clone();
+        externals.pin(|_| Ok(1)).unwrap();
+        assert_eq!(externals, pinned);
+
+        externals.unpin();
+        assert!(externals.definitions().all(|d| !d.is_pinned()));
+        let floating: Vec<_> = externals
+            .to_string()
+            .lines()
+            .skip(1)
+            .take(4)
+            .map(str::to_owned)
+            .collect();
+        assert_eq!(
+            floating,
+            vec![
+                "^/GDK_games/BLS/FuDaiLianLianCommon/tags/Release.009/source FuDaiLianLianCommon",
+                "^/GDK_games/BLS/NitroCommon/trunk/source NitroCommon",
+                "../../NitroParticles/trunk/source NitroParticles",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_definitions() {
+        for line in [
+            "NitroCommon",
+            "-r ^/NitroCommon NitroCommon",
+            "https://svn.ali.global/a https://svn.ali.global/b",
+            "-r5 ^/NitroCommon https://svn.ali.global/b",
+            "^/NitroCommon ../NitroCommon",
+            "^/NitroCommon \"Nitro Common",
+            "^/NitroCommon@abc NitroCommon",
+            "NitroCommon -r5 ^/NitroCommon",
+        ] {
+            assert!(ExternalDefinition::parse(line).is_err(), "{line}");
+        }
+        let error = ExternalDefinition::parse("^/NitroCommon NitroCommon\\").unwrap_err();
+        assert!(error.to_string().contains("trailing backslash"), "{error}");
+    }
+
+    const EXTERNALS: &str = r#"# shared libraries
+^/GDK_games/BLS/FuDaiLianLianCommon/tags/Release.009/source FuDaiLianLianCommon
+-r 310568 ^/GDK_games/BLS/NitroCommon/trunk/source NitroCommon
+-rHEAD ../../NitroParticles/trunk/source@305000 NitroParticles
+
+third_party/zlib -r21 https://svn.ali.global/vendor/zlib/trunk
+third_party/png https://svn.ali.global/vendor/png/trunk
+//svn.ali.global/GDK_games/assets/trunk "Shared Assets"
+/GDK_games/fonts/trunk Shared\ Fonts
+/svn/tools/trunk@2@ tools
+"#;
+}
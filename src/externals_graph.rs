@@ -0,0 +1,533 @@
+//! nested `svn:externals` resolved into a dependency graph
+
+use crate::{
+    errors::SvnError,
+    externals::{ExternalDefinition, ExternalFormat},
+    sub_commands::prop_get::SvnPropget,
+    types::RevisionType,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+
+/// reads the externals below `URL[@PEG]` at the operative revision, `-r REV`, returns the
+/// repository root url of the target and the output of `svn propget svn:externals --recursive`
+pub(crate) type ExternalsFetcher<'a> =
+    dyn Fn(&str, Option<RevisionType>) -> Result<(String, SvnPropget), SvnError> + 'a;
+
+/// one external found while walking the graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalNode {
+    /// local path relative to the root target
+    pub local_path: String,
+    /// absolute url of the external
+    pub url: String,
+    /// revision the external is checked out at, the operative revision or else the peg
+    /// revision, `None` when it follows HEAD
+    pub revision: Option<RevisionType>,
+    /// peg revision the url is looked up at, `-r` of the old format is also its peg
+    pub peg_revision: Option<RevisionType>,
+    /// an explicit revision number is set in the definition
+    pub pinned: bool,
+    /// 1 for externals defined below the root target, 2 for their externals and so on
+    pub depth: usize,
+    /// index of the external owning the definition, `None` for the root target
+    pub parent: Option<usize>,
+    /// definition as written in the property
+    pub definition: ExternalDefinition,
+    /// url is the one of an owner or a parent dir of it, its externals aren't read
+    pub cycle: bool,
+    /// index of the first external with the same url and revisions, whose children are the
+    /// ones of this external too, they aren't read again
+    pub same_as: Option<usize>,
+}
+
+/// every external reachable from a target, in the order they were found
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalsGraph {
+    /// url of the root target
+    pub root_url: String,
+    /// externals, parents come before their children
+    pub nodes: Vec<ExternalNode>,
+}
+
+// url, peg and operative revision of an external already walked, and its node
+type Walked = HashMap<(String, Option<RevisionType>, Option<RevisionType>), usize>;
+
+impl ExternalsGraph {
+    pub(crate) fn build(
+        root_url: &str,
+        root_revision: Option<RevisionType>,
+        fetcher: &ExternalsFetcher,
+    ) -> Result<Self, SvnError> {
+        let mut graph = Self {
+            root_url: root_url.to_owned(),
+            nodes: Vec::new(),
+        };
+        let mut chain = vec![root_url.to_owned()];
+        let root = (root_url, root_revision, None);
+        graph.walk(root, "", None, &mut chain, &mut Walked::new(), fetcher)?;
+        Ok(graph)
+    }
+
+    fn walk(
+        &mut self,
+        (url, peg, revision): (&str, Option<RevisionType>, Option<RevisionType>),
+        local_path: &str,
+        parent: Option<usize>,
+        chain: &mut Vec<String>,
+        walked: &mut Walked,
+        fetcher: &ExternalsFetcher,
+    ) -> Result<(), SvnError> {
+        // `-r REV URL@PEG` like svn checks out the external
+        let target = match peg {
+            Some(peg) => format!("{url}@{peg}"),
+            None => url.to_owned(),
+        };
+        let (repos_root, props) = fetcher(&target, revision)?;
+        let depth = chain.len();
+        for (owner_url, property) in props.values() {
+            let owner_dir = owner_url
+                .strip_prefix(url)
+                .unwrap_or_default()
+                .trim_matches('/');
+            for def in property.externals()?.definitions() {
+                let ext_url = def.resolve_url(owner_url, &repos_root)?;
+                let ext_path = [local_path, owner_dir, def.target.as_str()]
+                    .iter()
+                    .filter(|c| !c.is_empty())
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let cycle = chain
+                    .iter()
+                    .any(|c| *c == ext_url || c.starts_with(&format!("{ext_url}/")));
+                let ext_peg = match def.format {
+                    ExternalFormat::New => def.peg_revision,
+                    ExternalFormat::Old => def.revision,
+                };
+                let key = (ext_url.clone(), ext_peg, def.revision);
+                let same_as = walked.get(&key).copied();
+                let index = self.nodes.len();
+                self.nodes.push(ExternalNode {
+                    local_path: ext_path.clone(),
+                    url: ext_url.clone(),
+                    revision: def.revision.or(ext_peg),
+                    peg_revision: ext_peg,
+                    pinned: def.is_pinned(),
+                    depth,
+                    parent,
+                    definition: def.clone(),
+                    cycle,
+                    same_as,
+                });
+                if !cycle && same_as.is_none() {
+                    walked.insert(key, index);
+                    chain.push(ext_url.clone());
+                    self.walk(
+                        (&ext_url, ext_peg, def.revision),
+                        &ext_path,
+                        Some(index),
+                        chain,
+                        walked,
+                        fetcher,
+                    )?;
+                    chain.pop();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// externals defined by `parent`, `None` for the ones of the root target
+    pub fn children(&self, parent: Option<usize>) -> impl Iterator<Item = &ExternalNode> {
+        self.nodes.iter().filter(move |n| n.parent == parent)
+    }
+
+    /// externals pointing back to one of their owners
+    pub fn cycles(&self) -> impl Iterator<Item = &ExternalNode> {
+        self.nodes.iter().filter(|n| n.cycle)
+    }
+
+    /// urls pulled in at more than one revision, with every external pulling them in
+    pub fn revision_conflicts(&self) -> Vec<(&str, Vec<&ExternalNode>)> {
+        let mut by_url: BTreeMap<&str, Vec<&ExternalNode>> = BTreeMap::new();
+        for node in &self.nodes {
+            by_url.entry(node.url.as_str()).or_default().push(node);
+        }
+        by_url
+            .into_iter()
+            .filter(|(_, nodes)| nodes.iter().any(|n| n.revision != nodes[0].revision))
+            .collect()
+    }
+
+    /// graph as json, externals refer to their parent by index
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"root_url\":{},\"externals\":[",
+            json_str(&self.root_url)
+        );
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"local_path\":{},\"url\":{},\"revision\":{},\"peg_revision\":{},\"pinned\":{},\"depth\":{},\"parent\":{},\"cycle\":{},\"same_as\":{}}}",
+                json_str(&node.local_path),
+                json_str(&node.url),
+                json_revision(node.revision),
+                json_revision(node.peg_revision),
+                node.pinned,
+                node.depth,
+                json_index(node.parent),
+                node.cycle,
+                json_index(node.same_as),
+            );
+        }
+        json.push_str("]}");
+        json
+    }
+
+    /// graph in graphviz dot format, floating externals are drawn dashed and cycles red
+    pub fn to_dot(&self) -> String {
+        let mut dot = format!(
+            "digraph externals {{\n    root [label={}];\n",
+            dot_str(&self.root_url)
+        );
+        for (i, node) in self.nodes.iter().enumerate() {
+            // written like svn takes it, `-r REV URL@PEG`
+            let mut target = node.url.clone();
+            if let Some(peg) = node.peg_revision {
+                let _ = write!(target, "@{peg}");
+            }
+            match node.revision {
+                Some(revision) if node.peg_revision != Some(revision) => {
+                    target = format!("-r {revision} {target}");
+                }
+                None => target.push_str("@HEAD"),
+                _ => {}
+            }
+            let _ = writeln!(
+                dot,
+                "    n{i} [label={}];",
+                dot_str(&format!("{}\n{target}", node.local_path))
+            );
+            let parent = node
+                .parent
+                .map_or_else(|| "root".to_owned(), |p| format!("n{p}"));
+            let mut style = Vec::new();
+            if !node.pinned {
+                style.push("style=dashed");
+            }
+            if node.cycle {
+                style.push("color=red");
+            }
+            if style.is_empty() {
+                let _ = writeln!(dot, "    {parent} -> n{i};");
+            } else {
+                let _ = writeln!(dot, "    {parent} -> n{i} [{}];", style.join(", "));
+            }
+            if let Some(first) = node.same_as {
+                let _ = writeln!(dot, "    n{i} -> n{first} [style=dotted];");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn json_revision(revision: Option<RevisionType>) -> String {
+    revision.map_or_else(|| "null".to_owned(), |r| json_str(&r.to_string()))
+}
+
+fn json_index(index: Option<usize>) -> String {
+    index.map_or_else(|| "null".to_owned(), |i| i.to_string())
+}
+
+fn json_str(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn dot_str(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    const ROOT: &str = "https://svn.ali.global/GDK_games";
+
+    // fixtures by `URL[@PEG]` and `-r REV`
+    fn fetcher(
+        target: &str,
+        revision: Option<RevisionType>,
+    ) -> Result<(String, SvnPropget), SvnError> {
+        let fixtures = HashMap::from([
+            (
+                (
+                    "https://svn.ali.global/GDK_games/Peacock/trunk@400100",
+                    None,
+                ),
+                PEACOCK,
+            ),
+            (
+                ("https://svn.ali.global/GDK_games/Tiger/trunk", None),
+                TIGER,
+            ),
+            (
+                (
+                    "https://svn.ali.global/GDK_games/NitroCommon/tags/Release.003/source",
+                    None,
+                ),
+                NITRO_COMMON,
+            ),
+            (
+                (
+                    "https://svn.ali.global/GDK_games/NitroParticles/trunk/source",
+                    Some(RevisionType::Revision(310568)),
+                ),
+                NITRO_PARTICLES,
+            ),
+            (
+                (
+                    "https://svn.ali.global/GDK_games/NitroParticles/trunk/source",
+                    None,
+                ),
+                NITRO_PARTICLES,
+            ),
+        ]);
+        let xml = fixtures.get(&(target, revision)).copied().unwrap_or(EMPTY);
+        Ok((ROOT.to_owned(), SvnPropget::parse(xml)?))
+    }
+
+    fn graph() -> ExternalsGraph {
+        ExternalsGraph::build(
+            "https://svn.ali.global/GDK_games/Peacock/trunk",
+            Some(RevisionType::Revision(400100)),
+            &fetcher,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn walk_nested_externals() {
+        let graph = graph();
+        let nodes: Vec<_> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.local_path.as_str(), n.depth, n.parent, n.pinned, n.cycle))
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                ("source/lib/NitroCommon", 1, None, false, false),
+                (
+                    "source/lib/NitroCommon/lib/NitroParticles",
+                    2,
+                    Some(0),
+                    false,
+                    false
+                ),
+                (
+                    "source/lib/NitroCommon/lib/NitroParticles/Peacock",
+                    3,
+                    Some(1),
+                    false,
+                    true
+                ),
+                ("source/lib/NitroParticles", 1, None, true, false),
+                ("source/lib/NitroParticles/Peacock", 2, Some(3), false, true),
+            ]
+        );
+        assert_eq!(
+            graph.nodes[1].url,
+            "https://svn.ali.global/GDK_games/NitroParticles/trunk/source"
+        );
+        assert_eq!(graph.children(None).count(), 2);
+        assert_eq!(graph.cycles().count(), 2);
+
+        let conflicts = graph.revision_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].0,
+            "https://svn.ali.global/GDK_games/NitroParticles/trunk/source"
+        );
+        assert_eq!(conflicts[0].1.len(), 2);
+    }
+
+    #[test]
+    fn export() {
+        let graph = graph();
+        let json = graph.to_json();
+        assert!(json.starts_with(
+            r#"{"root_url":"https://svn.ali.global/GDK_games/Peacock/trunk","externals":[{"local_path":"source/lib/NitroCommon","url":"https://svn.ali.global/GDK_games/NitroCommon/tags/Release.003/source","revision":null,"peg_revision":null,"pinned":false,"depth":1,"parent":null,"cycle":false,"same_as":null},"#
+        ));
+        assert!(json.contains(
+            r#""revision":"310568","peg_revision":null,"pinned":true,"depth":1,"parent":null,"cycle":false,"same_as":null}"#
+        ));
+        assert!(json.ends_with("]}"));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph externals {\n    root [label=\"https://svn.ali.global/GDK_games/Peacock/trunk\"];\n"));
+        assert!(dot.contains("    n3 [label=\"source/lib/NitroParticles\\n-r 310568 https://svn.ali.global/GDK_games/NitroParticles/trunk/source\"];\n    root -> n3;\n"));
+        assert!(dot.contains("    n1 -> n2 [style=dashed, color=red];\n"));
+        assert!(dot.ends_with("}\n"));
+
+        let tiger = ExternalsGraph::build(
+            "https://svn.ali.global/GDK_games/Tiger/trunk",
+            None,
+            &fetcher,
+        )
+        .unwrap()
+        .to_dot();
+        assert!(tiger.contains("\\n-r 310000 https://svn.ali.global/GDK_games/NitroParticles/trunk/source@310568\"];\n"));
+        assert!(tiger.contains(
+            "\\nhttps://svn.ali.global/GDK_games/NitroParticles/trunk/source@290000\"];\n"
+        ));
+        assert!(tiger.contains("    n5 -> n0 [style=dotted];\n"));
+    }
+
+    #[test]
+    fn peg_and_operative_revision() {
+        let calls = RefCell::new(Vec::new());
+        let graph = ExternalsGraph::build(
+            "https://svn.ali.global/GDK_games/Tiger/trunk",
+            None,
+            &|target: &str, revision| {
+                calls.borrow_mut().push((target.to_owned(), revision));
+                fetcher(target, revision)
+            },
+        )
+        .unwrap();
+        let node = |path| graph.nodes.iter().find(|n| n.local_path == path).unwrap();
+        let particles = node("lib/Particles");
+        assert_eq!(particles.revision, Some(RevisionType::Revision(310000)));
+        assert_eq!(particles.peg_revision, Some(RevisionType::Revision(310568)));
+        assert!(calls.borrow().contains(&(
+            "https://svn.ali.global/GDK_games/NitroParticles/trunk/source@310568".to_owned(),
+            Some(RevisionType::Revision(310000))
+        )));
+        // old format, `-r` is the peg too
+        let legacy = node("lib/Legacy");
+        assert_eq!(legacy.peg_revision, Some(RevisionType::Revision(290000)));
+        assert!(calls.borrow().contains(&(
+            "https://svn.ali.global/GDK_games/NitroParticles/trunk/source@290000".to_owned(),
+            Some(RevisionType::Revision(290000))
+        )));
+    }
+
+    #[test]
+    fn shared_externals_read_once() {
+        let calls = RefCell::new(Vec::new());
+        let graph = ExternalsGraph::build(
+            "https://svn.ali.global/GDK_games/Tiger/trunk",
+            None,
+            &|target: &str, revision| {
+                calls.borrow_mut().push((target.to_owned(), revision));
+                fetcher(target, revision)
+            },
+        )
+        .unwrap();
+        let common = "https://svn.ali.global/GDK_games/NitroCommon/tags/Release.003/source";
+        assert_eq!(
+            calls.borrow().iter().filter(|(t, _)| t == common).count(),
+            1
+        );
+        let shared: Vec<_> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.url == common)
+            .map(|n| (n.local_path.as_str(), n.same_as))
+            .collect();
+        assert_eq!(
+            shared,
+            vec![("lib/NitroCommon", None), ("tools/NitroCommon", Some(0))]
+        );
+        let copy = graph
+            .nodes
+            .iter()
+            .position(|n| n.local_path == "tools/NitroCommon");
+        assert_eq!(graph.children(copy).count(), 0);
+    }
+
+    const PEACOCK: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="https://svn.ali.global/GDK_games/Peacock/trunk/source/lib">
+<property
+   name="svn:externals">^/NitroCommon/tags/Release.003/source NitroCommon
+-r310568 ^/NitroParticles/trunk/source NitroParticles
+</property>
+</target>
+</properties>
+"##;
+
+    const NITRO_COMMON: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="https://svn.ali.global/GDK_games/NitroCommon/tags/Release.003/source/lib">
+<property
+   name="svn:externals">../../../../../NitroParticles/trunk/source NitroParticles
+</property>
+</target>
+</properties>
+"##;
+
+    const NITRO_PARTICLES: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="https://svn.ali.global/GDK_games/NitroParticles/trunk/source">
+<property
+   name="svn:externals">^/Peacock Peacock
+</property>
+</target>
+</properties>
+"##;
+
+    const TIGER: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="https://svn.ali.global/GDK_games/Tiger/trunk/lib">
+<property
+   name="svn:externals">^/NitroCommon/tags/Release.003/source NitroCommon
+-r 310000 ^/NitroParticles/trunk/source@310568 Particles
+Legacy -r 290000 https://svn.ali.global/GDK_games/NitroParticles/trunk/source
+</property>
+</target>
+<target
+   path="https://svn.ali.global/GDK_games/Tiger/trunk/tools">
+<property
+   name="svn:externals">^/NitroCommon/tags/Release.003/source NitroCommon
+</property>
+</target>
+</properties>
+"##;
+
+    const EMPTY: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+</properties>
+"##;
+}
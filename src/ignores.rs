@@ -0,0 +1,405 @@
+//! svn:ignore and svn:global-ignores patterns
+
+use crate::{
+    sub_commands::prop_get::PropertyName, sub_commands::prop_list::SvnProplist, types::Optionals,
+};
+use std::{fs, path::PathBuf};
+
+/// `global-ignores` of svn when the client config doesn't set it
+pub const DEFAULT_GLOBAL_IGNORES: &str = "*.o *.lo *.la *.al .libs *.so *.so.[0-9]* *.a *.pyc \
+     *.pyo __pycache__ *.rej *~ #*# .#* .*.swp .DS_Store [Tt]humbs.db";
+
+/// property holding ignore patterns
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IgnoreProperty {
+    /// `svn:ignore`, one pattern per line, for the entries of the directory only
+    Ignore,
+    /// `svn:global-ignores`, whitespace separated, for everything below the directory
+    GlobalIgnores,
+}
+
+impl IgnoreProperty {
+    /// property name
+    pub fn name(&self) -> PropertyName {
+        match self {
+            Self::Ignore => PropertyName::SvnIgnore,
+            Self::GlobalIgnores => PropertyName::SvnGlobalIgnores,
+        }
+    }
+
+    /// patterns of a property value
+    pub fn patterns(&self, value: &str) -> Vec<String> {
+        let patterns: Box<dyn Iterator<Item = &str>> = match self {
+            Self::Ignore => Box::new(value.lines().map(str::trim)),
+            Self::GlobalIgnores => Box::new(value.split_whitespace()),
+        };
+        let mut unique: Vec<String> = Vec::new();
+        for pattern in patterns.filter(|p| !p.is_empty()) {
+            if !unique.iter().any(|u| u == pattern) {
+                unique.push(pattern.to_owned());
+            }
+        }
+        unique
+    }
+
+    /// value with `patterns` appended, existing and repeated patterns are kept once
+    pub fn add(&self, value: &str, patterns: &[&str]) -> String {
+        let mut all = self.patterns(value);
+        all.extend(patterns.iter().map(|p| p.to_string()));
+        Self::value(self.patterns(&all.join("\n")))
+    }
+
+    /// value without `patterns`, duplicates are dropped as well
+    pub fn remove(&self, value: &str, patterns: &[&str]) -> String {
+        Self::value(
+            self.patterns(value)
+                .into_iter()
+                .filter(|p| !patterns.contains(&p.as_str()))
+                .collect(),
+        )
+    }
+
+    // one pattern per line, valid for both properties
+    fn value(patterns: Vec<String>) -> String {
+        patterns.iter().map(|p| format!("{p}\n")).collect()
+    }
+}
+
+/// ignore patterns in effect for the entries of one directory
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IgnorePatterns {
+    /// `global-ignores` of the client config
+    pub config: Vec<String>,
+    /// `svn:global-ignores` of the directory and of its parents, with the path they are set on
+    pub global: Vec<(String, Vec<String>)>,
+    /// `svn:ignore` of the directory
+    pub local: Vec<String>,
+}
+
+impl IgnorePatterns {
+    /// collect the patterns out of `svn proplist --verbose --show-inherited-props DIR`
+    pub(crate) fn new(config: Vec<String>, props: &SvnProplist) -> Self {
+        let global_ignores = IgnoreProperty::GlobalIgnores;
+        let mut patterns = Self {
+            config,
+            ..Default::default()
+        };
+        for (path, props) in props.inherited_by_path() {
+            if let Some(value) = props.get(&global_ignores.name()) {
+                patterns.global.push((path, global_ignores.patterns(value)));
+            }
+        }
+        for (path, props) in props.by_path() {
+            if let Some(value) = props.get(&global_ignores.name()) {
+                patterns
+                    .global
+                    .push((path.clone(), global_ignores.patterns(value)));
+            }
+            if let Some(value) = props.get(&IgnoreProperty::Ignore.name()) {
+                patterns.local = IgnoreProperty::Ignore.patterns(value);
+            }
+        }
+        patterns
+    }
+
+    /// every pattern in effect
+    pub fn all(&self) -> impl Iterator<Item = &str> {
+        self.config
+            .iter()
+            .chain(self.global.iter().flat_map(|(_, p)| p.iter()))
+            .chain(self.local.iter())
+            .map(String::as_str)
+    }
+
+    /// the entry `name` of the directory matches one of the patterns
+    pub fn is_ignored(&self, name: &str) -> bool {
+        self.all().any(|pattern| fnmatch(pattern, name))
+    }
+}
+
+/// `global-ignores` svn runs with for the options: the last
+/// `--config-option config:miscellany:global-ignores=...`, else the `[miscellany]` section of
+/// the `config` file in `--config-dir` or the user's config dir, else svn's default
+pub fn client_global_ignores(options: &Optionals) -> Vec<String> {
+    let args: Vec<&str> = options
+        .config_options
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect();
+    let value = config_option(&args, "config", "miscellany", "global-ignores")
+        .or_else(|| {
+            let config_dir = match option_values(&args, "--config-dir").last() {
+                Some(dir) => Some(PathBuf::from(dir)),
+                None if cfg!(windows) => {
+                    std::env::var_os("APPDATA").map(|d| PathBuf::from(d).join("Subversion"))
+                }
+                None => std::env::var_os("HOME").map(|d| PathBuf::from(d).join(".subversion")),
+            };
+            let config = fs::read_to_string(config_dir?.join("config")).ok()?;
+            config_value(&config, "miscellany", "global-ignores")
+        })
+        .unwrap_or_else(|| DEFAULT_GLOBAL_IGNORES.to_owned());
+    IgnoreProperty::GlobalIgnores.patterns(&value)
+}
+
+// values of an option given as `OPTION VALUE` or `OPTION=VALUE`, in order
+fn option_values<'a>(args: &[&'a str], option: &str) -> Vec<&'a str> {
+    let mut values = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if *arg == option {
+            values.extend(args.next());
+        } else if let Some(value) = arg.strip_prefix(option).and_then(|a| a.strip_prefix('=')) {
+            values.push(value);
+        }
+    }
+    values
+}
+
+// value of the last `--config-option FILE:SECTION:OPTION=VALUE` setting the option
+fn config_option(args: &[&str], file: &str, section: &str, option: &str) -> Option<String> {
+    option_values(args, "--config-option")
+        .into_iter()
+        .rev()
+        .find_map(|value| {
+            let (key, value) = value.split_once('=')?;
+            let mut key = key.splitn(3, ':');
+            let matches = [file, section, option]
+                .iter()
+                .all(|name| key.next().is_some_and(|k| k.eq_ignore_ascii_case(name)));
+            matches.then(|| value.to_owned())
+        })
+}
+
+// value of an ini style option, lines starting with whitespace continue the value
+fn config_value(config: &str, section: &str, option: &str) -> Option<String> {
+    let mut in_section = false;
+    let mut value: Option<String> = None;
+    for line in config.lines() {
+        let trimmed = line.trim();
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            if value.is_some() {
+                break;
+            }
+            in_section = name.trim() == section;
+        } else if !in_section || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        } else if let Some(v) = value.as_mut().filter(|_| line.starts_with([' ', '\t'])) {
+            v.push(' ');
+            v.push_str(trimmed);
+        } else if value.is_some() {
+            break;
+        } else if let Some((_, v)) = trimmed
+            .split_once(['=', ':'])
+            .filter(|(key, _)| key.trim() == option)
+        {
+            value = Some(v.trim().to_owned());
+        }
+    }
+    value
+}
+
+// `apr_fnmatch` without flags, as svn matches ignore patterns: '*', '?', '[...]' and '\'
+fn fnmatch(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        let step = match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+                continue;
+            }
+            Some('?') => Some(p + 1),
+            Some('[') => match_class(&pattern, p, name[n]),
+            Some('\\') if p + 1 < pattern.len() => (pattern[p + 1] == name[n]).then_some(p + 2),
+            Some(&c) => (c == name[n]).then_some(p + 1),
+            None => None,
+        };
+        match (step, backtrack) {
+            (Some(next), _) => {
+                p = next;
+                n += 1;
+            }
+            (None, Some((star, from))) => {
+                p = star + 1;
+                n = from + 1;
+                backtrack = Some((star, from + 1));
+            }
+            (None, None) => return false,
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// match `c` against the class starting at `pattern[start] == '['`, returns the index after it
+fn match_class(pattern: &[char], start: usize, c: char) -> Option<usize> {
+    let mut i = start + 1;
+    let negate = matches!(pattern.get(i), Some('!' | '^'));
+    if negate {
+        i += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+    while let Some(&low) = pattern.get(i) {
+        if low == ']' && !first {
+            return (matched != negate).then_some(i + 1);
+        }
+        first = false;
+        if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2).is_some_and(|&h| h != ']') {
+            matched |= (low..=pattern[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= low == c;
+            i += 1;
+        }
+    }
+    // no closing ']', the '[' is an ordinary char
+    (c == '[').then_some(start + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove_patterns() {
+        let ignore = IgnoreProperty::Ignore;
+        let value = ignore.add("*.obj\nDebug\n*.obj\n", &["Release", "Debug", "my build"]);
+        assert_eq!(value, "*.obj\nDebug\nRelease\nmy build\n");
+        assert_eq!(
+            ignore.remove(&value, &["Debug", "x"]),
+            "*.obj\nRelease\nmy build\n"
+        );
+        assert_eq!(ignore.remove("Debug", &["Debug"]), "");
+
+        let global = IgnoreProperty::GlobalIgnores;
+        assert_eq!(
+            global.patterns("*.bak  *.log\n*.bak"),
+            vec!["*.bak", "*.log"]
+        );
+        assert_eq!(
+            global.add("*.bak *.log", &["*.tmp"]),
+            "*.bak\n*.log\n*.tmp\n"
+        );
+    }
+
+    #[test]
+    fn effective_patterns() {
+        let props = SvnProplist::parse(PROPS).unwrap();
+        let patterns = IgnorePatterns::new(vec!["*.o".to_owned()], &props);
+        assert_eq!(
+            patterns.global,
+            vec![
+                (
+                    "https://svn.ali.global/GDK_games".to_owned(),
+                    vec!["*.bak".to_owned()]
+                ),
+                (
+                    "source".to_owned(),
+                    vec!["*.log".to_owned(), "*.tmp".to_owned()]
+                ),
+            ]
+        );
+        assert_eq!(patterns.local, vec!["Debug", "*.vcxproj.user"]);
+        assert_eq!(
+            patterns.all().collect::<Vec<_>>(),
+            vec!["*.o", "*.bak", "*.log", "*.tmp", "Debug", "*.vcxproj.user"]
+        );
+        for ignored in ["main.o", "x.bak", "Debug", "Game.vcxproj.user", "build.log"] {
+            assert!(patterns.is_ignored(ignored), "{ignored}");
+        }
+        for kept in ["main.cpp", "Debug2", "Game.vcxproj", "log"] {
+            assert!(!patterns.is_ignored(kept), "{kept}");
+        }
+    }
+
+    #[test]
+    fn fnmatch_like_apr() {
+        assert!(fnmatch("*.so.[0-9]*", "libz.so.1.2"));
+        assert!(!fnmatch("*.so.[0-9]*", "libz.so.x"));
+        assert!(fnmatch("[Tt]humbs.db", "thumbs.db"));
+        assert!(fnmatch("[!a-c]?", "dx"));
+        assert!(!fnmatch("[!a-c]?", "bx"));
+        assert!(fnmatch("#*#", "#autosave#"));
+        assert!(fnmatch("\\*.txt", "*.txt"));
+        assert!(!fnmatch("\\*.txt", "a.txt"));
+        assert!(fnmatch("a*b*c", "aXbYbZc"));
+        assert!(fnmatch("[", "["));
+        assert!(fnmatch("*", ""));
+    }
+
+    #[test]
+    fn read_config() {
+        let config = "[auth]\nglobal-ignores = nope\n[miscellany]\n# global-ignores = *.x\n\
+            global-ignores = *.o *.lo\n  *.pyc\nenable-auto-props = yes\n";
+        assert_eq!(
+            config_value(config, "miscellany", "global-ignores").as_deref(),
+            Some("*.o *.lo *.pyc")
+        );
+        assert_eq!(config_value(config, "miscellany", "use-commit-times"), None);
+        assert!(IgnoreProperty::GlobalIgnores
+            .patterns(DEFAULT_GLOBAL_IGNORES)
+            .contains(&"[Tt]humbs.db".to_owned()));
+    }
+
+    #[test]
+    fn config_args() {
+        let config_dir =
+            std::env::temp_dir().join(format!("svn-cmd-config-{}", std::process::id()));
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("config"),
+            "[miscellany]\nglobal-ignores = *.obj *.pdb\n",
+        )
+        .unwrap();
+        let options = |config_options: String| Optionals {
+            config_options: Some(config_options),
+            ..Default::default()
+        };
+        let dir = config_dir.display();
+        assert_eq!(
+            client_global_ignores(&options(format!("--config-dir {dir}"))),
+            vec!["*.obj", "*.pdb"]
+        );
+        // a config option wins over the config file, the last one is taken
+        assert_eq!(
+            client_global_ignores(&options(format!(
+                "--config-dir={dir} --config-option config:miscellany:global-ignores=*.bak \
+                 --config-option servers:global:http-timeout=60 \
+                 --config-option=config:miscellany:global-ignores=*.log"
+            ))),
+            vec!["*.log"]
+        );
+        // no config file in the dir, svn's default
+        let empty = config_dir.join("empty");
+        assert_eq!(
+            client_global_ignores(&options(format!("--config-dir {}", empty.display()))),
+            IgnoreProperty::GlobalIgnores.patterns(DEFAULT_GLOBAL_IGNORES)
+        );
+        let _ = fs::remove_dir_all(config_dir);
+    }
+
+    const PROPS: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="https://svn.ali.global/GDK_games">
+<inherited_property
+   name="svn:global-ignores">*.bak
+</inherited_property>
+</target>
+<target
+   path="source">
+<property
+   name="svn:global-ignores">*.log *.tmp</property>
+<property
+   name="svn:ignore">Debug
+*.vcxproj.user
+</property>
+</target>
+</properties>
+"##;
+}
@@ -4,33 +4,83 @@
 
 mod cmd_wrapper;
 mod errors;
+mod externals;
+mod externals_graph;
+mod ignores;
+mod list_diff;
+mod merge_info;
+mod recovery;
+mod remote_tree;
 mod sub_commands;
 mod types;
+mod wc_format;
+mod xml_stream;
 
 pub use crate::{
     cmd_wrapper::{StderrFuture, StdoutFuture},
     errors::SvnError,
+    externals::{
+        ExternalDefinition, ExternalFormat, ExternalsChange, ExternalsLine, PinMode, SvnExternals,
+    },
+    externals_graph::{ExternalNode, ExternalsGraph},
+    ignores::{client_global_ignores, IgnorePatterns, IgnoreProperty, DEFAULT_GLOBAL_IGNORES},
+    list_diff::{ChangedEntry, ListDiff},
+    merge_info::{MergeInfo, RevisionRange},
+    recovery::{Recovered, Recovery, RecoveryPolicy, WC_CLEANUP_REQUIRED, WC_LOCKED},
+    remote_tree::{BreadthFirst, DepthFirst, RemoteTree, TreeNode},
     sub_commands::{
-        info::{EntryCommit, SvnInfo},
-        list::{ListEntry, SvnList, SvnListMap},
-        log::SvnLog,
-        prop_get::{PropertyName, SvnPropget},
-        prop_list::SvnProplist,
-        status::SvnStatus,
+        blame::{BlameLine, BlameMerged, BlameOptions, SvnBlame},
+        cleanup::CleanupOptions,
+        commit::CommitOptions,
+        diff::DiffOptions,
+        info::{
+            Conflict, ConflictFiles, ConflictVersion, EntryCommit, EntryLock, EntryRepository,
+            InfoEntry, InfoItem, InfoOptions, SvnInfo, TreeConflict, WcInfo,
+        },
+        list::{ListEntry, ListExternal, ListInspector, ListOptions, SvnList, SvnListMap},
+        lock::LockResult,
+        log::{LogEntry, SvnLog},
+        mergeinfo::{MergeinfoOptions, ShowRevs},
+        prop_get::{
+            ExternalPath, Property, PropertyName, PropgetOptions, RevProps, SvnPropget, Target,
+        },
+        prop_list::{PropertyMap, ProplistOptions, SvnProplist},
+        prop_set::{PropValue, PropsetOptions},
+        resolve::Accept,
+        revert::RevertOptions,
+        status::{
+            LockState, ReposStatus, StatusChangelist, StatusEntry, StatusItemType, StatusOptions,
+            StatusTarget, SvnStatus, WcStatus,
+        },
         version::CmdVersion,
     },
-    types::{Credentials, LoginOptions, Optionals, PathType},
+    types::{Credentials, Depth, LoginOptions, Optionals, PathType, RevisionType},
+    wc_format::{WcCompat, WcFormat},
+    xml_stream::XmlStream,
 };
 
 use crate::{
-    cmd_wrapper::SvnWrapper,
-    sub_commands::log::{RevCount, StartRev, XmlOut},
-    types::ToCmdArgs,
+    cmd_wrapper::{CmdOutput, SvnWrapper},
+    sub_commands::{
+        commit::commit_result,
+        lock::{parse_lock_output, unlocked_paths},
+        log::{RevCount, StartRev, XmlOut},
+        prop_set::ValueFile,
+    },
+    types::{ToCmdArgList, ToCmdArgs},
+    xml_stream::StdoutReader,
 };
 use log::trace;
 use rr_common_utils::{Future, JobDesc};
 use simple_broadcaster::Canceller;
-use std::{result::Result, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    io::BufRead,
+    path::{Path, PathBuf},
+    result::Result,
+    sync::{Arc, Mutex, OnceLock},
+};
 
 /// Accessor to svn command functionality
 #[derive(Debug, Clone)]
@@ -38,6 +88,10 @@ pub struct SvnCmd {
     #[allow(dead_code)]
     options: LoginOptions,
     extra_args: String,
+    cmd_version: Arc<OnceLock<CmdVersion>>,
+    recovery: Option<RecoveryPolicy>,
+    // set on the SvnCmd handed to a `recovering` closure only
+    recoveries: Option<Arc<Mutex<Vec<Recovery>>>>,
 }
 
 /// Builder to create SvnCmd
@@ -54,9 +108,37 @@ impl SvnCmd {
         SvnCmd {
             options,
             extra_args,
+            cmd_version: Arc::new(OnceLock::new()),
+            recovery: None,
+            recoveries: None,
         }
     }
 
+    /// recover from E155004 and E155037 with one `svn cleanup` of the working copy and a
+    /// retry, only on the working copies the policy allows, commit and lock included,
+    /// streaming and cancellable commands aren't retried, see `recovering` for the
+    /// recoveries attempted
+    pub fn with_recovery(mut self, policy: RecoveryPolicy) -> Self {
+        self.recovery = Some(policy);
+        self
+    }
+
+    /// run `op` with the recoveries of its commands reported back, separately from the
+    /// commands run by other callers at the same time
+    pub fn recovering<T>(&self, op: impl FnOnce(&SvnCmd) -> Result<T, SvnError>) -> Recovered<T> {
+        let recoveries = Arc::new(Mutex::new(Vec::new()));
+        let cmd = SvnCmd {
+            recoveries: Some(recoveries.clone()),
+            ..self.clone()
+        };
+        let result = op(&cmd);
+        let recoveries = recoveries
+            .lock()
+            .map(|mut r| std::mem::take(&mut *r))
+            .unwrap_or_default();
+        Recovered { result, recoveries }
+    }
+
     /// get svn version installed
     pub fn version() -> Result<CmdVersion, SvnError> {
         let out = SvnWrapper::new().common_cmd_runner(&["--version"])?;
@@ -74,6 +156,33 @@ impl SvnCmd {
         SvnList::parse(&xml_text)
     }
 
+    /// get list of files of one or more targets
+    /// `svn list [--depth ARG] [-r REV] [--search ARG] [--include-externals] TARGET[@PEG]...`
+    pub fn list_with_options(
+        &self,
+        targets: &[&str],
+        options: &ListOptions,
+    ) -> Result<SvnList, SvnError> {
+        let args = self.list_args(targets, options)?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let xml_text = self.get_cmd_out(&args)?;
+        trace!("{}", xml_text);
+        SvnList::parse(&xml_text)
+    }
+
+    /// get list of files, entries are parsed one by one while svn is still printing them
+    pub fn list_streaming(
+        &self,
+        targets: &[&str],
+        options: &ListOptions,
+        canceller: Canceller,
+    ) -> Result<(XmlStream<ListEntry>, StderrFuture), SvnError> {
+        let args = self.list_args(targets, options)?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let (stdout, stderr) = self.get_cmd_out_streaming(&args, canceller)?;
+        Ok((XmlStream::new(stdout, "entry"), stderr))
+    }
+
     /// get list of files
     pub fn list_cancellable(
         &self,
@@ -100,11 +209,58 @@ impl SvnCmd {
         SvnList::parse(xml_str)
     }
 
+    /// get list of files out of saved `svn list --xml` output, e.g. a file, one entry at a time
+    pub fn list_from_svn_list_xml_reader<R: BufRead + Send + 'static>(
+        &self,
+        reader: R,
+    ) -> XmlStream<ListEntry> {
+        XmlStream::from_buf_read(reader, "entry")
+    }
+
+    /// compare the listings of two targets, e.g. a tag and trunk, or one url at two
+    /// revisions with a different `revision` or `peg_revision` in the options of each side
+    pub fn list_diff(
+        &self,
+        old_target: &str,
+        old_options: &ListOptions,
+        new_target: &str,
+        new_options: &ListOptions,
+    ) -> Result<ListDiff, SvnError> {
+        let old = self.list_with_options(&[old_target], old_options)?;
+        let new = self.list_with_options(&[new_target], new_options)?;
+        Ok(ListDiff::new(&old, &new))
+    }
+
+    /// compare two saved outputs of `svn list --xml`
+    pub fn list_diff_from_svn_list_xml_output(
+        &self,
+        old_xml_str: &str,
+        new_xml_str: &str,
+    ) -> Result<ListDiff, SvnError> {
+        let old = self.list_from_svn_list_xml_output(old_xml_str)?;
+        let new = self.list_from_svn_list_xml_output(new_xml_str)?;
+        Ok(ListDiff::new(&old, &new))
+    }
+
     /// get diff
     pub fn diff() -> Result<(), SvnError> {
         Ok(())
     }
 
+    /// unified diff of working copy paths or between revisions
+    /// `svn diff [--depth ARG] [--changelist ARG] [-r START:END] TARGET...`
+    pub fn diff_with_options(
+        &self,
+        targets: &[&str],
+        options: &DiffOptions,
+    ) -> Result<String, SvnError> {
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["diff"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        self.get_cmd_out(&args)
+    }
+
     /// read file content
     pub fn cat(&self, target: &str) -> Result<String, SvnError> {
         self.get_cmd_out(&["cat", target])
@@ -119,6 +275,29 @@ impl SvnCmd {
         self.get_cmd_out_cancellable(&["cat", target], canceller)
     }
 
+    /// svn blame of a file, the lines come with their text, read from the working copy
+    /// file or with `svn cat` at the end of the revision range
+    /// `svn blame --xml [-r START:END] [--use-merge-history] [-x ARGS] TARGET`
+    pub fn blame(&self, target: &str, options: &BlameOptions) -> Result<SvnBlame, SvnError> {
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["blame", "--xml"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.push(target);
+        let blame = SvnBlame::parse(&self.get_cmd_out(&args)?)?;
+        let content = match options.revision {
+            Some((_, end)) => {
+                let end = end.to_string();
+                self.get_cmd_out(&["cat", "--revision", &end, target])?
+            }
+            // svn cat gives the pristine text, blame includes the local changes
+            None if Path::new(target).is_file() => {
+                String::from_utf8_lossy(&fs::read(target)?).into_owned()
+            }
+            None => self.cat(target)?,
+        };
+        blame.with_content(&content)
+    }
+
     /// SVN ADD command to add new files to stage for commit operation
     /// `svn add PATH`
     pub fn add(&self) -> Result<(), SvnError> {
@@ -131,6 +310,24 @@ impl SvnCmd {
         Ok(())
     }
 
+    /// commit working copy paths, the message is passed in a file so it may start with '-',
+    /// returns the new revision, `None` when nothing was changed
+    /// `svn commit --file MESSAGE [--depth ARG] [--changelist ARG] [--keep-changelists] PATH...`
+    pub fn commit_with_options(
+        &self,
+        targets: &[&str],
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<Option<u64>, SvnError> {
+        let message_file = ValueFile::new(message.as_bytes())?;
+        let message_path = message_file.path_str();
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["commit", "--file", &message_path];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        commit_result(self.get_cmd_output(&args)?)
+    }
+
     /// SVN CHECKOUT command: checkout files from mentioned repo url
     /// `svn checkout REPO_URL LOCAL_PATH`
     pub fn checkout(&self) -> Result<(), SvnError> {
@@ -151,13 +348,101 @@ impl SvnCmd {
         SvnLog::new(&args, target, Arc::new(SvnCmd::log_fetcher))
     }
 
+    /// SVN LOG command: read svn logs, entries are parsed one by one while svn is still
+    /// printing them
+    /// `svn log [-l LIMIT] REPO_URL | LOCAL_PATH`
+    pub fn log_streaming(
+        &self,
+        target: &str,
+        limit: Option<u32>,
+        canceller: Canceller,
+    ) -> Result<(XmlStream<LogEntry>, StderrFuture), SvnError> {
+        let limit = limit.map(|l| l.to_string());
+        let mut args = vec!["log", "--xml"];
+        if let Some(limit) = &limit {
+            args.extend(["--limit", limit]);
+        }
+        args.push(target);
+        let (stdout, stderr) = self.get_cmd_out_streaming(&args, canceller)?;
+        Ok((XmlStream::new(stdout, "logentry"), stderr))
+    }
+
     /// SVN STATUS command: svn path status
     /// `svn status PATH`
     pub fn status(&self, target: &str) -> Result<SvnStatus, SvnError> {
-        let out = self.get_cmd_out(&["status", "--xml", target])?;
+        self.status_with_options(&[target], &StatusOptions::default())
+    }
+
+    /// SVN STATUS command with remote check: svn path status against the repository HEAD
+    /// `svn status --show-updates PATH`
+    pub fn status_show_updates(&self, target: &str) -> Result<SvnStatus, SvnError> {
+        let options = StatusOptions {
+            show_updates: true,
+            ..Default::default()
+        };
+        self.status_with_options(&[target], &options)
+    }
+
+    /// SVN STATUS command with options: status of one or more paths
+    /// `svn status [--depth ARG] [-u] [--no-ignore] [--ignore-externals] [-q] [--changelist ARG] PATH...`
+    pub fn status_with_options(
+        &self,
+        targets: &[&str],
+        options: &StatusOptions,
+    ) -> Result<SvnStatus, SvnError> {
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["status", "--xml"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        let out = self.get_cmd_out(&args)?;
         SvnStatus::parse(out)
     }
 
+    /// SVN STATUS command with options, entries are parsed one by one while svn is still
+    /// printing them, the target and changelist grouping isn't reported
+    pub fn status_streaming(
+        &self,
+        targets: &[&str],
+        options: &StatusOptions,
+        canceller: Canceller,
+    ) -> Result<(XmlStream<StatusEntry>, StderrFuture), SvnError> {
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["status", "--xml"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        let (stdout, stderr) = self.get_cmd_out_streaming(&args, canceller)?;
+        Ok((XmlStream::new(stdout, "entry"), stderr))
+    }
+
+    /// put working copy files into a changelist, they leave the one they were in
+    /// `svn changelist NAME [--depth ARG] PATH...`
+    pub fn changelist_add(
+        &self,
+        name: &str,
+        paths: &[&str],
+        depth: Option<Depth>,
+    ) -> Result<(), SvnError> {
+        let mut args = vec!["changelist", name];
+        if let Some(depth) = depth {
+            args.extend(["--depth", depth.as_str()]);
+        }
+        args.extend_from_slice(paths);
+        self.get_cmd_out(&args).map(|_| ())
+    }
+
+    /// take working copy files out of their changelist
+    /// `svn changelist --remove PATH...`
+    pub fn changelist_remove(&self, paths: &[&str]) -> Result<(), SvnError> {
+        let mut args = vec!["changelist", "--remove"];
+        args.extend_from_slice(paths);
+        self.get_cmd_out(&args).map(|_| ())
+    }
+
+    /// files of a working copy by the changelist they belong to
+    pub fn changelists(&self, target: &str) -> Result<BTreeMap<String, Vec<PathBuf>>, SvnError> {
+        Ok(self.status(target)?.by_changelist())
+    }
+
     /// SVN INFO command: read svn info
     /// `svn info PATH`
     pub fn info(&self, target: &str) -> Result<SvnInfo, SvnError> {
@@ -165,18 +450,552 @@ impl SvnCmd {
         SvnInfo::parse(&out)
     }
 
+    /// SVN INFO command with options: read svn info of one or more paths
+    /// `svn info [--depth ARG] [--recursive] PATH...`
+    pub fn info_with_options(
+        &self,
+        targets: &[&str],
+        options: &InfoOptions,
+    ) -> Result<SvnInfo, SvnError> {
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["info", "--xml"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        let out = self.get_cmd_out(&args)?;
+        SvnInfo::parse(&out)
+    }
+
+    /// SVN INFO command for a single value
+    /// `svn info --show-item ITEM PATH`, falls back to parsing the full info on svn older than 1.9
+    pub fn info_show_item(&self, target: &str, item: InfoItem) -> Result<String, SvnError> {
+        if self.cmd_version()?.is_at_least(1, 9) {
+            let out =
+                self.get_cmd_out(&["info", "--show-item", item.as_str(), "--no-newline", target])?;
+            Ok(out.trim_end().to_owned())
+        } else {
+            let info = self.info(target)?;
+            info.entry()
+                .and_then(|entry| item.value_in(entry))
+                .ok_or_else(|| {
+                    SvnError::Other(format!(
+                        "'{}' isn't available for '{target}'",
+                        item.as_str()
+                    ))
+                })
+        }
+    }
+
+    /// working revision of a path, or the peg revision of an url
+    pub fn info_revision(&self, target: &str) -> Result<u64, SvnError> {
+        self.info_show_item_revision(target, InfoItem::Revision)
+    }
+
+    /// revision of the last change of a path
+    pub fn info_last_changed_revision(&self, target: &str) -> Result<u64, SvnError> {
+        self.info_show_item_revision(target, InfoItem::LastChangedRevision)
+    }
+
+    /// author of the last change of a path
+    pub fn info_last_changed_author(&self, target: &str) -> Result<String, SvnError> {
+        self.info_show_item(target, InfoItem::LastChangedAuthor)
+    }
+
+    /// date of the last change of a path
+    pub fn info_last_changed_date(&self, target: &str) -> Result<String, SvnError> {
+        self.info_show_item(target, InfoItem::LastChangedDate)
+    }
+
+    /// url of a path
+    pub fn info_url(&self, target: &str) -> Result<String, SvnError> {
+        self.info_show_item(target, InfoItem::Url)
+    }
+
+    /// url of a path relative to the repository root, '^/...'
+    pub fn info_relative_url(&self, target: &str) -> Result<String, SvnError> {
+        self.info_show_item(target, InfoItem::RelativeUrl)
+    }
+
+    /// repository root url of a path
+    pub fn info_repos_root_url(&self, target: &str) -> Result<String, SvnError> {
+        self.info_show_item(target, InfoItem::ReposRootUrl)
+    }
+
+    /// repository uuid of a path
+    pub fn info_repos_uuid(&self, target: &str) -> Result<String, SvnError> {
+        self.info_show_item(target, InfoItem::ReposUuid)
+    }
+
+    /// working copy root of a local path
+    pub fn info_wc_root(&self, target: &str) -> Result<PathBuf, SvnError> {
+        self.info_show_item(target, InfoItem::WcRoot)
+            .map(PathBuf::from)
+    }
+
     /// svn proplist <path>
     pub fn prop_list(&self, target: &str) -> Result<SvnProplist, SvnError> {
-        let out = self.get_cmd_out(&["proplist", "--xml", target])?;
+        self.prop_list_with_options(&[target], &ProplistOptions::default())
+    }
+
+    /// svn proplist with options, names and with `verbose` values of every target in one run
+    /// `svn proplist [--verbose] [--recursive] [--depth ARG] [--revprop] [-r REV] [--show-inherited-props] TARGET...`
+    pub fn prop_list_with_options(
+        &self,
+        targets: &[&str],
+        options: &ProplistOptions,
+    ) -> Result<SvnProplist, SvnError> {
+        if options.show_inherited_props && !self.cmd_version()?.is_at_least(1, 8) {
+            return Err(SvnError::Other(
+                "svn proplist --show-inherited-props needs svn 1.8 or newer".to_owned(),
+            ));
+        }
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["proplist", "--xml"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        let out = self.get_cmd_out(&args)?;
         SvnProplist::parse(&out)
     }
 
-    /// svn proplist <path>
+    /// svn propget <name> <path>
     pub fn prop_get(&self, target: &str, property: PropertyName) -> Result<SvnPropget, SvnError> {
-        let out = self.get_cmd_out(&["propget", property.as_str(), "--xml", target])?;
+        self.prop_get_with_options(&[target], &property, &PropgetOptions::default())
+    }
+
+    /// svn propget with options, the value of every target or of a revision property
+    /// `svn propget NAME [--recursive] [--depth ARG] [--revprop] [-r REV] [--show-inherited-props] TARGET...`
+    pub fn prop_get_with_options(
+        &self,
+        targets: &[&str],
+        property: &PropertyName,
+        options: &PropgetOptions,
+    ) -> Result<SvnPropget, SvnError> {
+        if options.show_inherited_props && !self.cmd_version()?.is_at_least(1, 8) {
+            return Err(SvnError::Other(
+                "svn propget --show-inherited-props needs svn 1.8 or newer".to_owned(),
+            ));
+        }
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["propget", property.as_str(), "--xml"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        let out = self.get_cmd_out(&args)?;
         SvnPropget::parse(&out)
     }
 
+    // value of a property of one target, `None` when not set, unlike propget proplist doesn't
+    // fail on a missing property
+    fn prop_value(
+        &self,
+        target: &str,
+        property: &PropertyName,
+    ) -> Result<Option<String>, SvnError> {
+        let options = ProplistOptions {
+            verbose: true,
+            depth: Some(Depth::Empty),
+            ..Default::default()
+        };
+        let props = self.prop_list_with_options(&[target], &options)?;
+        Ok(props
+            .by_path()
+            .into_values()
+            .find_map(|mut p| p.remove(property)))
+    }
+
+    /// svn propset <name> <value> <path>, the value is passed in a file so it may start with '-'
+    pub fn prop_set(
+        &self,
+        target: &str,
+        property: &PropertyName,
+        value: &str,
+    ) -> Result<(), SvnError> {
+        self.prop_set_with_options(
+            &[target],
+            property,
+            PropValue::Text(value),
+            &PropsetOptions::default(),
+        )
+    }
+
+    /// svn propset with options, on working copy paths or on a revision with `revprop`
+    /// `svn propset NAME --file FILE [--depth ARG] [--changelist ARG] [--force] [--revprop -r REV] TARGET...`
+    pub fn prop_set_with_options(
+        &self,
+        targets: &[&str],
+        property: &PropertyName,
+        value: PropValue,
+        options: &PropsetOptions,
+    ) -> Result<(), SvnError> {
+        let (file, _value_file) = value.file_arg()?;
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["propset", property.as_str(), "--file", &file];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        self.get_cmd_out(&args).map(|_| ())
+    }
+
+    /// svn propdel <name> <path>
+    pub fn prop_del(&self, target: &str, property: &PropertyName) -> Result<(), SvnError> {
+        self.prop_del_with_options(&[target], property, &PropsetOptions::default())
+    }
+
+    /// svn propdel with options, `force` is ignored
+    /// `svn propdel NAME [--depth ARG] [--changelist ARG] [--revprop -r REV] TARGET...`
+    pub fn prop_del_with_options(
+        &self,
+        targets: &[&str],
+        property: &PropertyName,
+        options: &PropsetOptions,
+    ) -> Result<(), SvnError> {
+        let options = PropsetOptions {
+            force: false,
+            ..options.clone()
+        };
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["propdel", property.as_str()];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        self.get_cmd_out(&args).map(|_| ())
+    }
+
+    /// set a revision property, the repository needs a pre-revprop-change hook allowing it
+    /// `svn propset NAME --file FILE --revprop -r REV URL`
+    pub fn revprop_set(
+        &self,
+        target: &str,
+        revision: u64,
+        property: &PropertyName,
+        value: &str,
+    ) -> Result<(), SvnError> {
+        let options = PropsetOptions {
+            revprop: true,
+            revision: Some(RevisionType::Revision(revision)),
+            ..Default::default()
+        };
+        self.prop_set_with_options(&[target], property, PropValue::Text(value), &options)
+    }
+
+    /// delete a revision property
+    /// `svn propdel NAME --revprop -r REV URL`
+    pub fn revprop_del(
+        &self,
+        target: &str,
+        revision: u64,
+        property: &PropertyName,
+    ) -> Result<(), SvnError> {
+        let options = PropsetOptions {
+            revprop: true,
+            revision: Some(RevisionType::Revision(revision)),
+            ..Default::default()
+        };
+        self.prop_del_with_options(&[target], property, &options)
+    }
+
+    /// replace the commit message of a revision, `svn:log`
+    pub fn revprop_set_log(
+        &self,
+        target: &str,
+        revision: u64,
+        message: &str,
+    ) -> Result<(), SvnError> {
+        self.revprop_set(target, revision, &PropertyName::SvnLog, message)
+    }
+
+    /// pin every floating `svn:externals` below a working copy path to an explicit revision,
+    /// or float them all to HEAD again, with `dry_run` the properties are left untouched
+    /// `svn propget svn:externals -R PATH` then `svn propset svn:externals VALUE DIR`
+    pub fn externals_pin(
+        &self,
+        target: &str,
+        mode: PinMode,
+        dry_run: bool,
+    ) -> Result<Vec<ExternalsChange>, SvnError> {
+        let options = PropgetOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let props = self.prop_get_with_options(&[target], &PropertyName::SvnExternals, &options)?;
+        let mut changes = Vec::new();
+        for (path, property) in props.values() {
+            let mut externals = property.externals()?;
+            let original = externals.clone();
+            match mode {
+                PinMode::CheckedOut => externals.pin(|def| {
+                    let checkout = Path::new(path).join(&def.target);
+                    self.info_revision(&checkout.to_string_lossy())
+                })?,
+                PinMode::Head => {
+                    let info = self.info(path)?;
+                    let owner = info
+                        .entry()
+                        .ok_or_else(|| SvnError::Other(format!("no info for '{path}'")))?;
+                    externals.pin(|def| self.info_revision(&def.resolved_url(owner)?))?
+                }
+                PinMode::Float => externals.unpin(),
+            }
+            // unchanged definitions keep their spelling, only pinned or floated lines differ
+            if externals != original {
+                let new = externals.to_string();
+                if !dry_run {
+                    self.prop_set(path, &PropertyName::SvnExternals, &new)?;
+                }
+                changes.push(ExternalsChange {
+                    path: path.to_owned(),
+                    old: property.value.clone(),
+                    new,
+                });
+            }
+        }
+        Ok(changes)
+    }
+
+    /// nested `svn:externals` below a url or working copy, read recursively into a graph,
+    /// a working copy is read from its url at its working revision
+    pub fn externals_graph(&self, target: &str) -> Result<ExternalsGraph, SvnError> {
+        let info = self.info(target)?;
+        let root = info
+            .entry()
+            .ok_or_else(|| SvnError::Other(format!("no info for '{target}'")))?;
+        let revision = root.revision.map(|r| RevisionType::Revision(r.into()));
+        ExternalsGraph::build(&root.url, revision, &|target: &str, revision| {
+            let repos_root = self.info_repos_root_url(target)?;
+            let options = PropgetOptions {
+                recursive: true,
+                revision,
+                ..Default::default()
+            };
+            let props =
+                self.prop_get_with_options(&[target], &PropertyName::SvnExternals, &options)?;
+            Ok((repos_root, props))
+        })
+    }
+
+    /// add patterns to `svn:ignore` or `svn:global-ignores` of a working copy directory,
+    /// patterns already set aren't repeated
+    pub fn ignore_add(
+        &self,
+        dir: &str,
+        property: IgnoreProperty,
+        patterns: &[&str],
+    ) -> Result<(), SvnError> {
+        let value = self.prop_value(dir, &property.name())?.unwrap_or_default();
+        let new = property.add(&value, patterns);
+        if new != value {
+            self.prop_set(dir, &property.name(), &new)?;
+        }
+        Ok(())
+    }
+
+    /// remove patterns from `svn:ignore` or `svn:global-ignores` of a working copy directory,
+    /// the property is deleted once empty
+    pub fn ignore_remove(
+        &self,
+        dir: &str,
+        property: IgnoreProperty,
+        patterns: &[&str],
+    ) -> Result<(), SvnError> {
+        let value = self.prop_value(dir, &property.name())?.unwrap_or_default();
+        let new = property.remove(&value, patterns);
+        if new.is_empty() && !value.is_empty() {
+            self.prop_del(dir, &property.name())?;
+        } else if new != value {
+            self.prop_set(dir, &property.name(), &new)?;
+        }
+        Ok(())
+    }
+
+    /// ignore patterns in effect for the entries of a working copy directory: the client
+    /// config `global-ignores`, the `svn:global-ignores` inherited or set on the directory
+    /// (svn 1.8+) and its `svn:ignore`
+    pub fn ignore_patterns(&self, dir: &str) -> Result<IgnorePatterns, SvnError> {
+        let options = ProplistOptions {
+            verbose: true,
+            depth: Some(Depth::Empty),
+            show_inherited_props: self.cmd_version()?.is_at_least(1, 8),
+            ..Default::default()
+        };
+        let props = self.prop_list_with_options(&[dir], &options)?;
+        Ok(IgnorePatterns::new(
+            client_global_ignores(&self.options.more),
+            &props,
+        ))
+    }
+
+    /// the working copy path would be ignored by svn status and svn add: it isn't versioned
+    /// and its name matches the patterns in effect for its parent directory
+    pub fn is_ignored(&self, path: &str) -> Result<bool, SvnError> {
+        // svn never ignores versioned items
+        if self.is_versioned(path)? {
+            return Ok(false);
+        }
+        let path = Path::new(path);
+        let name = path
+            .file_name()
+            .ok_or_else(|| SvnError::Other(format!("no file name in '{}'", path.display())))?;
+        let parent = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        let patterns = self.ignore_patterns(&parent.to_string_lossy())?;
+        Ok(patterns.is_ignored(&name.to_string_lossy()))
+    }
+
+    /// `svn:mergeinfo` of a working copy path or url, empty when not set
+    pub fn merge_info(&self, target: &str) -> Result<MergeInfo, SvnError> {
+        let value = self.prop_value(target, &PropertyName::SvnMergeinfo)?;
+        MergeInfo::parse(&value.unwrap_or_default())
+    }
+
+    /// revisions of `source` merged or not into `target`, by `options.show_revs`
+    /// `svn mergeinfo --show-revs eligible|merged [--recursive] [--depth ARG] [-r START:END] SOURCE TARGET`
+    pub fn mergeinfo_with_options(
+        &self,
+        source: &str,
+        target: &str,
+        options: &MergeinfoOptions,
+    ) -> Result<Vec<RevisionRange>, SvnError> {
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["mergeinfo"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend([source, target]);
+        let out = self.get_cmd_out(&args)?;
+        sub_commands::mergeinfo::parse_revisions(&out)
+    }
+
+    /// revisions of `source` not merged into `target` yet, e.g. the trunk revisions missing
+    /// on a release branch
+    pub fn unmerged_revisions(&self, source: &str, target: &str) -> Result<Vec<u64>, SvnError> {
+        self.mergeinfo_revision_list(source, target, ShowRevs::Eligible)
+    }
+
+    /// revisions of `source` already merged into `target`
+    pub fn merged_revisions(&self, source: &str, target: &str) -> Result<Vec<u64>, SvnError> {
+        self.mergeinfo_revision_list(source, target, ShowRevs::Merged)
+    }
+
+    fn mergeinfo_revision_list(
+        &self,
+        source: &str,
+        target: &str,
+        show_revs: ShowRevs,
+    ) -> Result<Vec<u64>, SvnError> {
+        let options = MergeinfoOptions {
+            show_revs,
+            ..Default::default()
+        };
+        let ranges = self.mergeinfo_with_options(source, target, &options)?;
+        Ok(ranges.iter().flat_map(RevisionRange::revisions).collect())
+    }
+
+    /// lock working copy paths or urls, with `force` a lock held by someone else is stolen,
+    /// the paths already locked are reported as failures, W160035
+    /// `svn lock [--file COMMENT] [--force] TARGET...`
+    pub fn lock(
+        &self,
+        targets: &[&str],
+        comment: Option<&str>,
+        force: bool,
+    ) -> Result<Vec<LockResult>, SvnError> {
+        let comment_file = comment.map(|c| ValueFile::new(c.as_bytes())).transpose()?;
+        let comment_path = comment_file.as_ref().map(ValueFile::path_str);
+        let mut args = vec!["lock"];
+        if let Some(path) = &comment_path {
+            args.extend(["--file", path]);
+        }
+        if force {
+            args.push("--force");
+        }
+        args.extend_from_slice(targets);
+        parse_lock_output(&self.get_cmd_output(&args)?)
+    }
+
+    /// unlock working copy paths or urls, with `force` a lock held by someone else is broken
+    /// `svn unlock [--force] TARGET...`
+    pub fn unlock(&self, targets: &[&str], force: bool) -> Result<Vec<LockResult>, SvnError> {
+        let mut args = vec!["unlock"];
+        if force {
+            args.push("--force");
+        }
+        args.extend_from_slice(targets);
+        parse_lock_output(&self.get_cmd_output(&args)?)
+    }
+
+    /// lock of a single path or url, `None` when not locked
+    pub fn lock_info(&self, target: &str) -> Result<Option<EntryLock>, SvnError> {
+        Ok(self.info(target)?.entry().and_then(|e| e.lock.clone()))
+    }
+
+    /// locks held below a url, by the path relative to it
+    pub fn locks(&self, target: &str) -> Result<Vec<(String, EntryLock)>, SvnError> {
+        let options = ListOptions {
+            depth: Some(Depth::Infinity),
+            ..Default::default()
+        };
+        let list = self.list_with_options(&[target], &options)?;
+        Ok(list
+            .lists
+            .iter()
+            .flat_map(|l| l.entry.iter().flatten())
+            .filter_map(|e| Some((e.name.clone()?, e.lock.clone()?)))
+            .collect())
+    }
+
+    /// files below a url or working copy having `svn:needs-lock` and nobody holding their
+    /// lock, by the path relative to it, a working copy is read from its url
+    pub fn needs_lock_unlocked(&self, target: &str) -> Result<Vec<String>, SvnError> {
+        // propget prints local paths for a working copy, the locks are matched by url
+        let url = self.info_url(target)?;
+        let options = PropgetOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let props = self.prop_get_with_options(&[&url], &PropertyName::SvnNeedsLock, &options)?;
+        let locked: HashSet<String> = self.locks(&url)?.into_iter().map(|(p, _)| p).collect();
+        Ok(unlocked_paths(
+            &url,
+            props.values().map(|(path, _)| path),
+            &locked,
+        ))
+    }
+
+    /// rewrite the repository urls of a working copy when the server moved, e.g.
+    /// `https://old-host/svn` to `https://new-host/svn`
+    /// `svn relocate FROM-PREFIX TO-PREFIX PATH`
+    pub fn relocate(
+        &self,
+        from_prefix: &str,
+        to_prefix: &str,
+        wc_path: &str,
+    ) -> Result<(), SvnError> {
+        self.get_cmd_out(&["relocate", from_prefix, to_prefix, wc_path])
+            .map(|_| ())
+    }
+
+    /// upgrade a working copy to the format of the installed svn
+    /// `svn upgrade PATH`
+    pub fn upgrade(&self, wc_path: &str) -> Result<(), SvnError> {
+        self.get_cmd_out(&["upgrade", wc_path]).map(|_| ())
+    }
+
+    /// format of the working copy holding a path, read from disk without running svn,
+    /// `None` when the path isn't in a working copy
+    pub fn wc_format(&self, path: &str) -> Result<Option<WcFormat>, SvnError> {
+        WcFormat::detect(Path::new(path))
+    }
+
+    /// whether the installed svn can work with the working copy holding a path, from its
+    /// format on disk or, when unreadable, from the E155036 or E155021 of `svn info`
+    pub fn wc_compat(&self, path: &str) -> Result<WcCompat, SvnError> {
+        if let Ok(Some(format)) = WcFormat::detect(Path::new(path)) {
+            return Ok(format.compat(self.cmd_version()?.version()));
+        }
+        match self.get_cmd_out(&["info", "--xml", path]) {
+            Ok(_) => Ok(WcCompat::Compatible),
+            Err(SvnError::WcNeedsUpgrade(_)) => Ok(WcCompat::NeedsUpgrade),
+            Err(SvnError::WcTooNew(_)) => Ok(WcCompat::TooNew),
+            Err(e) => Err(e),
+        }
+    }
+
     /// SVN DELETE command: delete file/dir from remote url
     /// `svn delete PATH`
     pub fn delete(&self) -> Result<(), SvnError> {
@@ -189,6 +1008,69 @@ impl SvnCmd {
         Ok(())
     }
 
+    /// revert local changes of working copy paths
+    /// `svn revert [--depth ARG] [--recursive] [--changelist ARG] PATH...`
+    pub fn revert_with_options(
+        &self,
+        targets: &[&str],
+        options: &RevertOptions,
+    ) -> Result<(), SvnError> {
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["revert"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.extend_from_slice(targets);
+        self.get_cmd_out(&args).map(|_| ())
+    }
+
+    /// recover a working copy left locked by an interrupted command, and with the options
+    /// drop unversioned or ignored files and unused pristines
+    /// `svn cleanup [--remove-unversioned] [--remove-ignored] [--vacuum-pristines] [--include-externals] PATH`
+    pub fn cleanup(&self, target: &str, options: &CleanupOptions) -> Result<(), SvnError> {
+        match options.min_version() {
+            Some((major, minor)) if !self.cmd_version()?.is_at_least(major, minor) => {
+                return Err(SvnError::Other(format!(
+                    "svn cleanup {} needs svn {major}.{minor} or newer",
+                    options.to_cmd_arg_list().join(" ")
+                )));
+            }
+            _ => {}
+        }
+        let option_args = options.to_cmd_arg_list();
+        let mut args = vec!["cleanup"];
+        args.extend(option_args.iter().map(String::as_str));
+        args.push(target);
+        self.get_cmd_out(&args).map(|_| ())
+    }
+
+    /// mark conflicted working copy paths resolved, keeping the version chosen by `accept`
+    /// `svn resolve --accept ARG PATH...`
+    pub fn resolve(&self, paths: &[&str], accept: Accept) -> Result<(), SvnError> {
+        let mut args = vec!["resolve", "--accept", accept.as_str()];
+        args.extend_from_slice(paths);
+        self.get_cmd_out(&args).map(|_| ())
+    }
+
+    /// conflicted paths of a working copy with their '.mine', '.rOLD' and '.rNEW' files and
+    /// tree conflicts, `svn status` finds them and `svn info` describes them
+    pub fn conflicts(&self, target: &str) -> Result<Vec<ConflictFiles>, SvnError> {
+        let status = self.status(target)?;
+        let conflicted: Vec<String> = status
+            .entries()
+            .filter(|e| {
+                e.wc_status.item == StatusItemType::Conflicted
+                    || e.wc_status.props == StatusItemType::Conflicted
+                    || e.wc_status.tree_conflicted
+            })
+            .map(|e| e.path.to_string_lossy().into_owned())
+            .collect();
+        if conflicted.is_empty() {
+            return Ok(Vec::new());
+        }
+        let paths: Vec<&str> = conflicted.iter().map(String::as_str).collect();
+        let info = self.info_with_options(&paths, &InfoOptions::default())?;
+        Ok(info.conflicts())
+    }
+
     /// SVN COPY command: copy from svn repo path to mentioned path
     /// `svn copy CURR_PATH NEW_PATH`
     pub fn copy_to(&self) -> Result<(), SvnError> {
@@ -229,7 +1111,53 @@ impl SvnCmd {
 
 // following is for private methods
 impl SvnCmd {
+    fn cmd_version(&self) -> Result<&CmdVersion, SvnError> {
+        if self.cmd_version.get().is_none() {
+            let version = SvnCmd::version()?;
+            let _ = self.cmd_version.set(version);
+        }
+        Ok(self.cmd_version.get().unwrap()) // it has been set above, so unwrap is fine here
+    }
+
+    fn list_args(&self, targets: &[&str], options: &ListOptions) -> Result<Vec<String>, SvnError> {
+        if !options.search.is_empty() && !self.cmd_version()?.is_at_least(1, 10) {
+            return Err(SvnError::Other(
+                "svn list --search needs svn 1.10 or newer".to_owned(),
+            ));
+        }
+        let mut args = vec!["list".to_owned(), "--xml".to_owned()];
+        args.extend(options.to_cmd_arg_list());
+        args.extend(targets.iter().map(|t| options.target_arg(t)));
+        Ok(args)
+    }
+
+    fn info_show_item_revision(&self, target: &str, item: InfoItem) -> Result<u64, SvnError> {
+        let out = self.info_show_item(target, item)?;
+        out.parse::<u64>()
+            .map_err(|e| SvnError::Other(format!("invalid revision: {out:?}, e: {e:?}")))
+    }
+
+    // whether svn status knows the path as a versioned item, a path which doesn't exist and
+    // isn't versioned either is reported with W155010
+    fn is_versioned(&self, path: &str) -> Result<bool, SvnError> {
+        let args = ["status", "--xml", "--depth", "empty", "--no-ignore", path];
+        let out = self.get_cmd_output(&args)?;
+        if out.stderr.contains("svn: warning: W155010:") {
+            return Ok(false);
+        }
+        let status = SvnStatus::parse(out.into_stdout()?)?;
+        Ok(status.entries().any(|e| {
+            !matches!(
+                e.wc_status.item,
+                StatusItemType::Unversioned | StatusItemType::Ignored | StatusItemType::None
+            )
+        }))
+    }
+
     fn get_cmd_out(&self, args: &[&str]) -> Result<String, SvnError> {
+        if self.recovery.is_some() {
+            return self.get_cmd_output(args)?.into_stdout();
+        }
         let mut all_args: Vec<&str> = Vec::new();
         all_args.extend_from_slice(args);
         self.extra_args
@@ -238,6 +1166,28 @@ impl SvnCmd {
         SvnWrapper::new().common_cmd_runner(&all_args)
     }
 
+    // output of a command, with the recovery of the policy when one is set
+    fn get_cmd_output(&self, args: &[&str]) -> Result<CmdOutput, SvnError> {
+        let run = |args: &[&str]| {
+            let mut all_args: Vec<&str> = Vec::new();
+            all_args.extend_from_slice(args);
+            self.extra_args
+                .split_whitespace()
+                .for_each(|s| all_args.push(s));
+            SvnWrapper::new().common_cmd_runner_output(&all_args)
+        };
+        let Some(policy) = &self.recovery else {
+            return run(args);
+        };
+        let (out, recovery) = policy.run(args, run);
+        if let (Some(recovery), Some(Ok(mut recoveries))) =
+            (recovery, self.recoveries.as_ref().map(|r| r.lock()))
+        {
+            recoveries.push(recovery);
+        }
+        out
+    }
+
     fn get_cmd_out_cancellable(
         &self,
         args: &[&str],
@@ -251,6 +1201,19 @@ impl SvnCmd {
         SvnWrapper::new().common_cmd_runner_cancellable(&all_args, canceller)
     }
 
+    fn get_cmd_out_streaming(
+        &self,
+        args: &[&str],
+        canceller: Canceller,
+    ) -> Result<(StdoutReader, StderrFuture), SvnError> {
+        let mut all_args: Vec<&str> = Vec::new();
+        all_args.extend_from_slice(args);
+        self.extra_args
+            .split_whitespace()
+            .for_each(|s| all_args.push(s));
+        SvnWrapper::new().common_cmd_runner_streaming(&all_args, canceller)
+    }
+
     fn log_fetcher(
         args: String,
         target: String,
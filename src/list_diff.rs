@@ -0,0 +1,265 @@
+//! comparison of two svn list snapshots
+
+use crate::{
+    sub_commands::list::{ListEntry, SvnList},
+    types::PathType,
+};
+use std::collections::HashMap;
+
+/// differences between two `SvnList`s, entries are matched by their relative path
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ListDiff {
+    /// entries only present in the new list
+    pub added: Vec<ListEntry>,
+    /// entries only present in the old list
+    pub removed: Vec<ListEntry>,
+    /// entries present in both lists with a different size or last commit
+    pub changed: Vec<ChangedEntry>,
+}
+
+/// entry present in both lists
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedEntry {
+    /// relative path name
+    pub name: String,
+    /// entry in the old list
+    pub old: ListEntry,
+    /// entry in the new list
+    pub new: ListEntry,
+}
+
+impl ChangedEntry {
+    /// is file or dir
+    pub fn kind(&self) -> &PathType {
+        &self.new.kind
+    }
+
+    /// file size differs
+    pub fn is_resized(&self) -> bool {
+        self.old.size != self.new.size
+    }
+
+    /// last commit revision differs
+    pub fn is_recommitted(&self) -> bool {
+        self.old.commit.revision != self.new.commit.revision
+    }
+}
+
+impl ListDiff {
+    /// compare two lists, an entry which changed between file and dir is reported
+    /// as removed and added, with several targets the n-th `<list>` blocks are compared
+    pub fn new(old: &SvnList, new: &SvnList) -> Self {
+        let old_entries = named_entries(old);
+        let new_entries = named_entries(new);
+        let old_map: HashMap<_, _> = old_entries.iter().copied().collect();
+        let new_map: HashMap<_, _> = new_entries.iter().copied().collect();
+        let mut diff = ListDiff::default();
+        for &(key, new_entry) in &new_entries {
+            match old_map.get(&key) {
+                Some(old_entry) if old_entry.kind == new_entry.kind => {
+                    if old_entry.size != new_entry.size
+                        || old_entry.commit.revision != new_entry.commit.revision
+                    {
+                        diff.changed.push(ChangedEntry {
+                            name: key.1.to_owned(),
+                            old: (*old_entry).clone(),
+                            new: new_entry.clone(),
+                        });
+                    }
+                }
+                Some(old_entry) => {
+                    diff.removed.push((*old_entry).clone());
+                    diff.added.push(new_entry.clone());
+                }
+                None => diff.added.push(new_entry.clone()),
+            }
+        }
+        for &(key, old_entry) in &old_entries {
+            if !new_map.contains_key(&key) {
+                diff.removed.push(old_entry.clone());
+            }
+        }
+        diff
+    }
+
+    /// true if both lists have the same entries with the same sizes and commits
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// changed entries whose size differs
+    pub fn resized(&self) -> impl Iterator<Item = &ChangedEntry> {
+        self.changed.iter().filter(|c| c.is_resized())
+    }
+
+    /// changed entries whose last commit differs
+    pub fn recommitted(&self) -> impl Iterator<Item = &ChangedEntry> {
+        self.changed.iter().filter(|c| c.is_recommitted())
+    }
+}
+
+// entries having a name keyed by their `<list>` block and name, in the order svn listed them
+fn named_entries(list: &SvnList) -> Vec<((usize, &str), &ListEntry)> {
+    list.lists
+        .iter()
+        .enumerate()
+        .flat_map(|(i, l)| l.entry.iter().flatten().map(move |e| (i, e)))
+        .filter_map(|(i, e)| e.name.as_deref().map(|name| ((i, name), e)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_snapshots() {
+        let old = SvnList::parse(LIST_OLD).unwrap();
+        let new = SvnList::parse(LIST_NEW).unwrap();
+        let diff = ListDiff::new(&old, &new);
+        let names = |entries: &[ListEntry]| {
+            entries
+                .iter()
+                .map(|e| e.name.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&diff.added), vec!["ReadMe.txt", "libs"]);
+        assert_eq!(names(&diff.removed), vec!["libs", "configure"]);
+        let resized: Vec<_> = diff.resized().map(|c| c.name.as_str()).collect();
+        assert_eq!(resized, vec!["manifest"]);
+        let recommitted: Vec<_> = diff.recommitted().map(|c| c.name.as_str()).collect();
+        assert_eq!(recommitted, vec!["manifest", "src"]);
+        assert_eq!(diff.changed[1].kind(), &PathType::Dir);
+        assert!(!diff.is_empty());
+        assert!(ListDiff::new(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn diff_per_target() {
+        // the same name in the second target isn't matched against the first one
+        let mut old = SvnList::parse(LIST_OLD).unwrap();
+        old.lists.push(old.lists[0].clone());
+        let mut new = old.clone();
+        let entries = new.lists[1].entry.as_mut().unwrap();
+        entries.retain(|e| e.name.as_deref() != Some("manifest"));
+        let diff = ListDiff::new(&old, &new);
+        assert!(diff.added.is_empty() && diff.changed.is_empty());
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name.as_deref(), Some("manifest"));
+    }
+
+    const LIST_OLD: &str = r##"
+<?xml version="1.0" encoding="UTF-8"?>
+<lists>
+<list
+   path="https://svn.ali.global/GDK_games/GDK_games/BLS/CDS/PurpleCelebration/tags/RC01/source">
+<entry
+   kind="file">
+<name>Makefile.in</name>
+<size>420</size>
+<commit
+   revision="373439">
+<author>sa102001</author>
+<date>2023-07-21T05:31:45.995541Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>libs</name>
+<size>12</size>
+<commit
+   revision="373439">
+<author>sa102001</author>
+<date>2023-07-21T05:31:45.995541Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>configure</name>
+<size>1542</size>
+<commit
+   revision="373439">
+<author>sa102001</author>
+<date>2023-07-21T05:31:45.995541Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>manifest</name>
+<size>14107</size>
+<commit
+   revision="373439">
+<author>sa102001</author>
+<date>2023-07-21T05:31:45.995541Z</date>
+</commit>
+</entry>
+<entry
+   kind="dir">
+<name>src</name>
+<commit
+   revision="382999">
+<author>sa102001</author>
+<date>2024-01-23T09:48:16.976029Z</date>
+</commit>
+</entry>
+</list>
+</lists>
+    "##;
+
+    const LIST_NEW: &str = r##"
+<?xml version="1.0" encoding="UTF-8"?>
+<lists>
+<list
+   path="https://svn.ali.global/GDK_games/GDK_games/BLS/CDS/PurpleCelebration/tags/RC02/source">
+<entry
+   kind="file">
+<name>Makefile.in</name>
+<size>420</size>
+<commit
+   revision="373439">
+<author>sa102001</author>
+<date>2023-07-21T05:31:45.995541Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>ReadMe.txt</name>
+<size>15414</size>
+<commit
+   revision="386704">
+<author>sa102001</author>
+<date>2024-03-26T03:12:16.268312Z</date>
+</commit>
+</entry>
+<entry
+   kind="dir">
+<name>libs</name>
+<commit
+   revision="386704">
+<author>sa102001</author>
+<date>2024-03-26T03:12:16.268312Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>manifest</name>
+<size>14200</size>
+<commit
+   revision="386704">
+<author>sa102001</author>
+<date>2024-03-26T03:12:16.268312Z</date>
+</commit>
+</entry>
+<entry
+   kind="dir">
+<name>src</name>
+<commit
+   revision="386704">
+<author>sa102001</author>
+<date>2024-03-26T03:12:16.268312Z</date>
+</commit>
+</entry>
+</list>
+</lists>
+    "##;
+}
@@ -0,0 +1,343 @@
+//! `svn:mergeinfo` parser and writer
+
+use crate::errors::SvnError;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+/// revisions `start..=end` merged from a source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RevisionRange {
+    /// first revision
+    pub start: u64,
+    /// last revision, same as `start` for a single revision
+    pub end: u64,
+    /// `false` for the ranges marked with `*`, merged into the directory itself but not
+    /// into its children
+    pub inheritable: bool,
+}
+
+impl RevisionRange {
+    /// inheritable range
+    pub fn new(start: u64, end: u64) -> Self {
+        Self {
+            start,
+            end,
+            inheritable: true,
+        }
+    }
+
+    /// `revision` is in the range
+    pub fn contains(&self, revision: u64) -> bool {
+        (self.start..=self.end).contains(&revision)
+    }
+
+    /// every revision of the range
+    pub fn revisions(&self) -> impl Iterator<Item = u64> {
+        self.start..=self.end
+    }
+
+    /// parse `N`, `N-M`, `N*` or `N-M*`
+    fn parse(range: &str) -> Option<Self> {
+        let range = range.trim();
+        let (range, inheritable) = match range.strip_suffix('*') {
+            Some(range) => (range, false),
+            None => (range, true),
+        };
+        let (start, end) = range.split_once('-').unwrap_or((range, range));
+        let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+        (start > 0 && start <= end).then_some(Self {
+            start,
+            end,
+            inheritable,
+        })
+    }
+}
+
+impl Display for RevisionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.start)?;
+        if self.end != self.start {
+            write!(f, "-{}", self.end)?;
+        }
+        if !self.inheritable {
+            write!(f, "*")?;
+        }
+        Ok(())
+    }
+}
+
+/// value of `svn:mergeinfo`, the revisions merged from every source
+///
+/// ranges are kept sorted and merged, a revision being both inheritable and not is
+/// inheritable
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MergeInfo {
+    /// repository relative path of the source, e.g. `/trunk`, and its merged ranges
+    pub sources: BTreeMap<String, Vec<RevisionRange>>,
+}
+
+impl MergeInfo {
+    /// parse a property value, one `PATH:RANGE,RANGE...` line per source
+    pub fn parse(value: &str) -> Result<Self, SvnError> {
+        let mut merge_info = Self::default();
+        for line in value.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let invalid = || SvnError::InvalidMergeinfo(line.to_owned());
+            let (source, ranges) = line.rsplit_once(':').ok_or_else(invalid)?;
+            if source.is_empty() {
+                return Err(invalid());
+            }
+            let ranges = ranges
+                .split(',')
+                .map(RevisionRange::parse)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?;
+            merge_info.add(source, &ranges);
+        }
+        Ok(merge_info)
+    }
+
+    /// merge info of a single source
+    pub fn from_ranges(source: &str, ranges: &[RevisionRange]) -> Self {
+        let mut merge_info = Self::default();
+        merge_info.add(source, ranges);
+        merge_info
+    }
+
+    /// add ranges merged from `source`
+    pub fn add(&mut self, source: &str, ranges: &[RevisionRange]) {
+        let entry = self.sources.entry(source.to_owned()).or_default();
+        entry.extend_from_slice(ranges);
+        *entry = normalize(entry);
+        if entry.is_empty() {
+            self.sources.remove(source);
+        }
+    }
+
+    /// ranges merged from `source`
+    pub fn ranges(&self, source: &str) -> &[RevisionRange] {
+        self.sources.get(source).map_or(&[], Vec::as_slice)
+    }
+
+    /// `revision` of `source` is merged
+    pub fn contains(&self, source: &str, revision: u64) -> bool {
+        find(self.ranges(source), revision).is_some()
+    }
+
+    /// revisions merged in either
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a.max(b))
+    }
+
+    /// revisions merged here but not in `other`, whether inheritable or not
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| if b.is_some() { None } else { a })
+    }
+
+    /// revisions merged in both, inheritable only when they are in both
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a.min(b))
+    }
+
+    // apply `op` to the state of every revision, `None` not merged, `Some(inheritable)`
+    fn combine(
+        &self,
+        other: &Self,
+        op: impl Fn(Option<bool>, Option<bool>) -> Option<bool>,
+    ) -> Self {
+        let mut sources = BTreeMap::new();
+        let keys: BTreeSet<_> = self.sources.keys().chain(other.sources.keys()).collect();
+        for source in keys {
+            let (a, b) = (self.ranges(source), other.ranges(source));
+            let mut ranges = Vec::new();
+            for pair in bounds(a.iter().chain(b)).windows(2) {
+                let state = op(find(a, pair[0]), find(b, pair[0]));
+                if let Some(inheritable) = state {
+                    push(&mut ranges, pair[0], pair[1] - 1, inheritable);
+                }
+            }
+            if !ranges.is_empty() {
+                sources.insert(source.clone(), ranges);
+            }
+        }
+        Self { sources }
+    }
+}
+
+impl Display for MergeInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (source, ranges)) in self.sources.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{source}:")?;
+            for (j, range) in ranges.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{range}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// inheritability of `revision` in sorted, non overlapping ranges
+fn find(ranges: &[RevisionRange], revision: u64) -> Option<bool> {
+    let i = ranges.partition_point(|r| r.end < revision);
+    ranges
+        .get(i)
+        .filter(|r| r.contains(revision))
+        .map(|r| r.inheritable)
+}
+
+// append `start..=end`, joined to the last range when adjacent and alike
+fn push(ranges: &mut Vec<RevisionRange>, start: u64, end: u64, inheritable: bool) {
+    match ranges.last_mut() {
+        Some(last) if last.end + 1 == start && last.inheritable == inheritable => last.end = end,
+        _ => ranges.push(RevisionRange {
+            start,
+            end,
+            inheritable,
+        }),
+    }
+}
+
+// sort and merge overlapping or adjacent ranges
+fn normalize(ranges: &[RevisionRange]) -> Vec<RevisionRange> {
+    let mut merged = Vec::new();
+    for pair in bounds(ranges.iter()).windows(2) {
+        let state = ranges
+            .iter()
+            .filter(|r| r.contains(pair[0]))
+            .map(|r| r.inheritable)
+            .max();
+        if let Some(inheritable) = state {
+            push(&mut merged, pair[0], pair[1] - 1, inheritable);
+        }
+    }
+    merged
+}
+
+// every revision starting or following a range, ranges don't cross them
+fn bounds<'a>(ranges: impl Iterator<Item = &'a RevisionRange>) -> Vec<u64> {
+    let mut bounds: Vec<u64> = ranges.flat_map(|r| [r.start, r.end + 1]).collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(value: &str) -> Vec<RevisionRange> {
+        MergeInfo::parse(&format!("/trunk:{value}"))
+            .unwrap()
+            .ranges("/trunk")
+            .to_vec()
+    }
+
+    #[test]
+    fn parse_and_write() {
+        let merge_info = MergeInfo::parse(MERGEINFO).unwrap();
+        assert_eq!(merge_info.sources.len(), 2);
+        assert_eq!(
+            merge_info.ranges("/GDK_games/Peacock/trunk"),
+            [
+                RevisionRange::new(300100, 300120),
+                RevisionRange::new(300125, 300125),
+                RevisionRange {
+                    start: 300200,
+                    end: 300200,
+                    inheritable: false
+                },
+            ]
+        );
+        assert!(merge_info.contains("/GDK_games/Peacock/trunk", 300110));
+        assert!(!merge_info.contains("/GDK_games/Peacock/trunk", 300121));
+        assert!(!merge_info.contains("/branches/x", 300110));
+        assert_eq!(
+            merge_info.to_string(),
+            "/GDK_games/Peacock/branches/RC03:310000-310004\n\
+             /GDK_games/Peacock/trunk:300100-300120,300125,300200*"
+        );
+        assert_eq!(
+            MergeInfo::parse(&merge_info.to_string()).unwrap(),
+            merge_info
+        );
+        assert_eq!(MergeInfo::parse("").unwrap(), MergeInfo::default());
+
+        for invalid in [
+            "/trunk",
+            "/trunk:",
+            "/trunk:5-3",
+            "/trunk:0",
+            ":1-2",
+            "/trunk:a",
+        ] {
+            assert!(
+                matches!(
+                    MergeInfo::parse(invalid),
+                    Err(SvnError::InvalidMergeinfo(_))
+                ),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_ranges() {
+        assert_eq!(
+            ranges("7,1-3,4-5,2"),
+            [RevisionRange::new(1, 5), RevisionRange::new(7, 7)]
+        );
+        assert_eq!(
+            ranges("1-10*,3-4")
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            ["1-2*", "3-4", "5-10*"]
+        );
+        assert_eq!(
+            ranges("1-4*,1-2")
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            ["1-2", "3-4*"]
+        );
+        assert_eq!(
+            ranges("1-4,2-6*")
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            ["1-4", "5-6*"]
+        );
+        assert_eq!(
+            ranges("1-4*,2-6")
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            ["1*", "2-6"]
+        );
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = MergeInfo::parse("/trunk:1-10,15*\n/branches/a:3").unwrap();
+        let b = MergeInfo::parse("/trunk:5-12*,15\n/branches/b:7").unwrap();
+        assert_eq!(
+            a.union(&b).to_string(),
+            "/branches/a:3\n/branches/b:7\n/trunk:1-10,11-12*,15"
+        );
+        assert_eq!(a.difference(&b).to_string(), "/branches/a:3\n/trunk:1-4");
+        assert_eq!(a.intersection(&b).to_string(), "/trunk:5-10*,15*");
+        assert_eq!(a.difference(&a), MergeInfo::default());
+        assert_eq!(a.union(&MergeInfo::default()), a);
+    }
+
+    const MERGEINFO: &str = "/GDK_games/Peacock/trunk:300100-300120,300125,300200*
+/GDK_games/Peacock/branches/RC03:310000-310004
+";
+}
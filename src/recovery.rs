@@ -0,0 +1,323 @@
+//! recovery of working copies left locked by an interrupted svn command
+
+use crate::{
+    cmd_wrapper::{stderr_error, CmdOutput},
+    errors::SvnError,
+};
+use log::trace;
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+/// working copy locked, `Working copy 'PATH' locked`
+pub const WC_LOCKED: &str = "E155004";
+/// previous operation has not finished, run 'cleanup' if it was interrupted
+pub const WC_CLEANUP_REQUIRED: &str = "E155037";
+
+/// options of the svn commands run by SvnCmd which take a value, the value isn't a target
+const VALUE_OPTIONS: &[&str] = &[
+    "-r",
+    "--revision",
+    "-c",
+    "--change",
+    "-m",
+    "--message",
+    "-F",
+    "--file",
+    "-l",
+    "--limit",
+    "-x",
+    "--extensions",
+    "--depth",
+    "--set-depth",
+    "--cl",
+    "--changelist",
+    "--accept",
+    "--search",
+    "--show-item",
+    "--show-revs",
+    "--targets",
+    "--encoding",
+    "--username",
+    "--password",
+    "--config-dir",
+    "--config-option",
+];
+
+/// opt-in policy of `SvnCmd::with_recovery`: a command failing with E155004 or E155037 on a
+/// working copy inside `allowed_paths` gets one `svn cleanup` of that working copy and is
+/// run again
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryPolicy {
+    /// dirs whose working copies `svn cleanup` may run on, a lock anywhere else fails as usual
+    pub allowed_paths: Vec<PathBuf>,
+}
+
+/// one recovery attempt, see `SvnCmd::recovering`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovery {
+    /// arguments of the failed command
+    pub command: Vec<String>,
+    /// error code which triggered the recovery
+    pub code: String,
+    /// working copy root `svn cleanup` ran on
+    pub cleaned_path: PathBuf,
+    /// cleanup and retry both succeeded
+    pub recovered: bool,
+    /// error of the cleanup or of the retry
+    pub error: Option<String>,
+}
+
+/// result of the commands run by `SvnCmd::recovering`, with the recoveries they needed
+#[derive(Debug)]
+pub struct Recovered<T> {
+    /// result of the commands, after the recoveries
+    pub result: Result<T, SvnError>,
+    /// recoveries attempted while running the commands, in order
+    pub recoveries: Vec<Recovery>,
+}
+
+impl RecoveryPolicy {
+    /// error code and working copy root to clean up for the stderr of a failed command,
+    /// `None` when it isn't a recoverable lock or the working copy isn't allowed
+    pub(crate) fn recoverable(&self, stderr: &str, args: &[&str]) -> Option<(String, PathBuf)> {
+        let code = [WC_LOCKED, WC_CLEANUP_REQUIRED]
+            .into_iter()
+            .find(|code| stderr.contains(&format!("svn: {code}:")))?;
+        static LOCKED: OnceLock<Regex> = OnceLock::new();
+        let locked = LOCKED
+            .get_or_init(|| {
+                Regex::new(r"(?:Working copy '([^']+)' locked|'([^']+)' is already locked)")
+                    .unwrap()
+            })
+            .captures(stderr)
+            .and_then(|c| c.get(1).or(c.get(2)))
+            .map(|m| vec![m.as_str()]);
+        // no path in the message, the local targets the command ran on
+        let candidates = locked.unwrap_or_else(|| targets(args));
+        candidates
+            .into_iter()
+            .filter_map(|path| wc_root(Path::new(path)))
+            .find(|root| self.allows(root))
+            .map(|root| (code.to_owned(), root))
+    }
+
+    /// run a command with `run`, a recoverable failure gets `svn cleanup` of the working copy
+    /// and one retry, the attempt is returned with the output of the retry
+    pub(crate) fn run(
+        &self,
+        args: &[&str],
+        run: impl Fn(&[&str]) -> Result<CmdOutput, SvnError>,
+    ) -> (Result<CmdOutput, SvnError>, Option<Recovery>) {
+        let out = match run(args) {
+            Ok(out) if !out.success => out,
+            result => return (result, None),
+        };
+        let Some((code, root)) = self.recoverable(&out.stderr, args) else {
+            return (Ok(out), None);
+        };
+        let root_str = root.to_string_lossy();
+        let result = run(&["cleanup", root_str.as_ref()]).and_then(|cleanup| {
+            if cleanup.success {
+                run(args)
+            } else {
+                Err(stderr_error(cleanup.stderr))
+            }
+        });
+        trace!(
+            "{code} on {root:?}, cleanup and retry: {:?}",
+            result.is_ok()
+        );
+        let error = match &result {
+            Ok(retry) if retry.success => None,
+            Ok(retry) => Some(retry.stderr.clone()),
+            Err(e) => Some(e.to_string()),
+        };
+        let recovery = Recovery {
+            command: args.iter().map(|a| a.to_string()).collect(),
+            code,
+            cleaned_path: root,
+            recovered: error.is_none(),
+            error,
+        };
+        (result, Some(recovery))
+    }
+
+    // whether `root` is one of the allowed paths or inside one
+    fn allows(&self, root: &Path) -> bool {
+        self.allowed_paths.iter().any(|allowed| {
+            fs::canonicalize(allowed)
+                .map(|allowed| root.starts_with(allowed))
+                .unwrap_or(false)
+        })
+    }
+}
+
+// target args of a command, skipping the subcommand, the options and their values
+fn targets<'a>(args: &[&'a str]) -> Vec<&'a str> {
+    let mut targets = Vec::new();
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        if VALUE_OPTIONS.contains(arg) {
+            rest.next();
+        } else if !arg.starts_with('-') {
+            targets.push(*arg);
+        }
+    }
+    targets
+}
+
+// root of the working copy holding `path`, the closest dir with a `.svn` dir
+fn wc_root(path: &Path) -> Option<PathBuf> {
+    let path = fs::canonicalize(path).ok()?;
+    path.ancestors()
+        .find(|dir| dir.join(".svn").is_dir())
+        .map(Path::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recoverable_locks() {
+        let jobs = std::env::temp_dir().join(format!("svn-cmd-jobs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&jobs);
+        fs::create_dir_all(jobs.join("wc/.svn")).unwrap();
+        fs::create_dir_all(jobs.join("wc/source")).unwrap();
+        fs::create_dir_all(jobs.join("other/.svn")).unwrap();
+        let jobs = fs::canonicalize(jobs).unwrap();
+        let wc = jobs.join("wc");
+        let source = wc.join("source");
+        let source = source.to_str().unwrap();
+        // the parent dir of the working copies is allowed, cleanup runs on the working copy
+        let policy = RecoveryPolicy {
+            allowed_paths: vec![jobs.clone()],
+        };
+        let locked = format!(
+            "svn: E155004: Run 'svn cleanup' to remove locks (type 'svn help cleanup' for details)
+svn: E155004: Working copy '{source}' locked.
+svn: E155004: '{source}' is already locked.
+"
+        );
+        assert_eq!(
+            policy.recoverable(&locked, &["update", source]),
+            Some((WC_LOCKED.to_owned(), wc.clone()))
+        );
+
+        let only_wc = RecoveryPolicy {
+            allowed_paths: vec![wc.clone()],
+        };
+        let other = jobs.join("other");
+        let elsewhere = format!("svn: E155004: Working copy '{}' locked.\n", other.display());
+        assert_eq!(only_wc.recoverable(&elsewhere, &["update", source]), None);
+        // not a working copy
+        let outside = format!("svn: E155004: Working copy '{}' locked.\n", jobs.display());
+        assert_eq!(policy.recoverable(&outside, &["update", source]), None);
+
+        let unfinished = "svn: E155037: Previous operation has not finished; run 'cleanup' if it was interrupted\n";
+        assert_eq!(
+            policy.recoverable(unfinished, &["status", "--xml", source]),
+            Some((WC_CLEANUP_REQUIRED.to_owned(), wc.clone()))
+        );
+        // option values and the subcommand aren't targets
+        let message = other.join("message.txt");
+        fs::write(&message, "").unwrap();
+        let message = message.to_str().unwrap();
+        assert_eq!(
+            policy.recoverable(unfinished, &["commit", "--file", message, "/missing/wc"]),
+            None
+        );
+        assert_eq!(
+            policy.recoverable(unfinished, &["commit", "--file", message, source]),
+            Some((WC_CLEANUP_REQUIRED.to_owned(), wc.clone()))
+        );
+
+        let other = "svn: E170013: Unable to connect to a repository\n";
+        assert_eq!(policy.recoverable(other, &["status", source]), None);
+        let _ = fs::remove_dir_all(jobs);
+    }
+
+    #[test]
+    fn retry_commit() {
+        let jobs = std::env::temp_dir().join(format!("svn-cmd-retry-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&jobs);
+        fs::create_dir_all(jobs.join("wc/.svn")).unwrap();
+        let wc = fs::canonicalize(jobs.join("wc")).unwrap();
+        let wc_str = wc.to_str().unwrap().to_owned();
+        let policy = RecoveryPolicy {
+            allowed_paths: vec![jobs.clone()],
+        };
+        let commit = ["commit", "--file", "message.txt", wc_str.as_str()];
+        let runs = std::cell::RefCell::new(Vec::new());
+        let run = |args: &[&str]| {
+            let mut runs = runs.borrow_mut();
+            runs.push(args.join(" "));
+            Ok(match runs.len() {
+                1 => CmdOutput {
+                    success: false,
+                    stdout: String::new(),
+                    stderr: format!("svn: E155004: Working copy '{wc_str}' locked.\n"),
+                },
+                2 => CmdOutput {
+                    success: true,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                },
+                _ => CmdOutput {
+                    success: true,
+                    stdout: "Committed revision 400101.\n".to_owned(),
+                    stderr: String::new(),
+                },
+            })
+        };
+        let (out, recovery) = policy.run(&commit, run);
+        assert_eq!(out.unwrap().stdout, "Committed revision 400101.\n");
+        assert_eq!(
+            *runs.borrow(),
+            vec![
+                commit.join(" "),
+                format!("cleanup {wc_str}"),
+                commit.join(" ")
+            ]
+        );
+        assert_eq!(
+            recovery,
+            Some(Recovery {
+                command: commit.iter().map(|a| a.to_string()).collect(),
+                code: WC_LOCKED.to_owned(),
+                cleaned_path: wc.clone(),
+                recovered: true,
+                error: None,
+            })
+        );
+
+        // not recoverable, run once and returned as is
+        runs.borrow_mut().clear();
+        let failed = |args: &[&str]| {
+            runs.borrow_mut().push(args.join(" "));
+            Ok(CmdOutput {
+                success: false,
+                stdout: String::new(),
+                stderr: "svn: E170013: Unable to connect to a repository\n".to_owned(),
+            })
+        };
+        let (out, recovery) = policy.run(&commit, failed);
+        assert!(!out.unwrap().success);
+        assert!(recovery.is_none());
+        assert_eq!(runs.borrow().len(), 1);
+        let _ = fs::remove_dir_all(jobs);
+    }
+
+    #[test]
+    fn target_args() {
+        assert_eq!(
+            targets(&["commit", "--depth", "empty", "-F", "msg.txt", "--xml", "a", "b"]),
+            vec!["a", "b"]
+        );
+        assert_eq!(targets(&["status"]), Vec::<&str>::new());
+    }
+}
@@ -0,0 +1,360 @@
+//! hierarchical model of a recursive svn list
+
+use crate::{
+    sub_commands::{
+        info::EntryCommit,
+        list::{ListEntry, SvnList},
+    },
+    types::PathType,
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::{self, Display},
+};
+
+/// tree of the entries of `svn list --recursive`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteTree {
+    root: TreeNode,
+}
+
+/// file or dir in a `RemoteTree`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    /// last path component, or the listed target for the root
+    pub name: String,
+    /// is file or dir
+    pub kind: PathType,
+    /// the listed entry, `None` for the root and for dirs which weren't listed themselves
+    pub entry: Option<ListEntry>,
+    children: BTreeMap<String, TreeNode>,
+}
+
+impl RemoteTree {
+    /// build the tree out of every entry of the list, entries without a name are skipped
+    pub fn new(list: &SvnList) -> Self {
+        let root_name = list
+            .lists
+            .first()
+            .and_then(|l| l.path.clone())
+            .unwrap_or_else(|| ".".to_owned());
+        let mut root = TreeNode::dir(root_name);
+        if let Some(entries) = list.iter_opt() {
+            for entry in entries {
+                if let Some(name) = entry.name.as_deref() {
+                    root.insert(name, entry);
+                }
+            }
+        }
+        Self { root }
+    }
+
+    /// root node, the listed target
+    pub fn root(&self) -> &TreeNode {
+        &self.root
+    }
+
+    /// node at a '/' separated path relative to the root
+    pub fn get(&self, path: &str) -> Option<&TreeNode> {
+        path.split('/')
+            .filter(|c| !c.is_empty())
+            .try_fold(&self.root, |node, component| node.child(component))
+    }
+
+    /// visit every node below the root, a dir is followed by all of its descendants
+    pub fn iter_depth_first(&self) -> DepthFirst<'_> {
+        DepthFirst {
+            stack: self.root.children_with_path(""),
+        }
+    }
+
+    /// visit every node below the root, level by level
+    pub fn iter_breadth_first(&self) -> BreadthFirst<'_> {
+        let mut queue = self.root.children_with_path("");
+        queue.reverse();
+        BreadthFirst {
+            queue: queue.into(),
+        }
+    }
+}
+
+impl TreeNode {
+    fn dir(name: String) -> Self {
+        Self {
+            name,
+            kind: PathType::Dir,
+            entry: None,
+            children: BTreeMap::new(),
+        }
+    }
+
+    fn insert(&mut self, path: &str, entry: &ListEntry) {
+        match path.split_once('/') {
+            Some((component, rest)) if !rest.is_empty() => self
+                .children
+                .entry(component.to_owned())
+                .or_insert_with(|| TreeNode::dir(component.to_owned()))
+                .insert(rest, entry),
+            _ => {
+                let name = path.trim_end_matches('/');
+                let node = self
+                    .children
+                    .entry(name.to_owned())
+                    .or_insert_with(|| TreeNode::dir(name.to_owned()));
+                node.kind = entry.kind.clone();
+                node.entry = Some(entry.clone());
+            }
+        }
+    }
+
+    /// child by name
+    pub fn child(&self, name: &str) -> Option<&TreeNode> {
+        self.children.get(name)
+    }
+
+    /// children sorted by name
+    pub fn children(&self) -> impl Iterator<Item = &TreeNode> {
+        self.children.values()
+    }
+
+    /// sum of the sizes of every file in the subtree, the file size for files
+    pub fn total_size(&self) -> usize {
+        self.entry.as_ref().and_then(|e| e.size).unwrap_or(0)
+            + self.children().map(TreeNode::total_size).sum::<usize>()
+    }
+
+    /// commit with the highest revision in the subtree
+    pub fn newest_commit(&self) -> Option<&EntryCommit> {
+        self.entry
+            .as_ref()
+            .map(|e| &e.commit)
+            .into_iter()
+            .chain(self.children().filter_map(TreeNode::newest_commit))
+            .max_by_key(|c| c.revision)
+    }
+
+    // children in reverse order, so popping from the end yields them sorted
+    fn children_with_path(&self, parent: &str) -> Vec<(String, &TreeNode)> {
+        self.children
+            .values()
+            .rev()
+            .map(|child| {
+                let path = if parent.is_empty() {
+                    child.name.clone()
+                } else {
+                    format!("{parent}/{}", child.name)
+                };
+                (path, child)
+            })
+            .collect()
+    }
+
+    fn fmt_children(&self, f: &mut fmt::Formatter<'_>, prefix: &str) -> fmt::Result {
+        let count = self.children.len();
+        for (i, child) in self.children().enumerate() {
+            let last = i + 1 == count;
+            writeln!(
+                f,
+                "{prefix}{}{}",
+                if last { "└── " } else { "├── " },
+                child.name
+            )?;
+            let child_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            child.fmt_children(f, &child_prefix)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for RemoteTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.root.name)?;
+        self.root.fmt_children(f, "")
+    }
+}
+
+impl From<&SvnList> for RemoteTree {
+    fn from(list: &SvnList) -> Self {
+        Self::new(list)
+    }
+}
+
+/// depth first walk over a `RemoteTree`, yields '/' separated paths with their nodes
+#[derive(Clone)]
+pub struct DepthFirst<'a> {
+    stack: Vec<(String, &'a TreeNode)>,
+}
+
+impl<'a> Iterator for DepthFirst<'a> {
+    type Item = (String, &'a TreeNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, node) = self.stack.pop()?;
+        self.stack.extend(node.children_with_path(&path));
+        Some((path, node))
+    }
+}
+
+/// breadth first walk over a `RemoteTree`, yields '/' separated paths with their nodes
+#[derive(Clone)]
+pub struct BreadthFirst<'a> {
+    queue: VecDeque<(String, &'a TreeNode)>,
+}
+
+impl<'a> Iterator for BreadthFirst<'a> {
+    type Item = (String, &'a TreeNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, node) = self.queue.pop_front()?;
+        self.queue
+            .extend(node.children_with_path(&path).into_iter().rev());
+        Some((path, node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_and_query() {
+        let list = SvnList::parse(LIST_RECURSIVE).unwrap();
+        let tree = RemoteTree::new(&list);
+        assert_eq!(
+            tree.root().name,
+            "https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source"
+        );
+        let assets = tree.get("assets").unwrap();
+        assert_eq!(assets.kind, PathType::Dir);
+        assert_eq!(assets.total_size(), 2029056 + 47);
+        assert_eq!(assets.newest_commit().unwrap().revision, 322279);
+        assert_eq!(tree.get("assets/reels/reels.png").unwrap().total_size(), 47);
+        assert!(tree.get("assets/missing").is_none());
+
+        // 'docs' wasn't listed itself, only its file
+        let docs = tree.get("docs").unwrap();
+        assert_eq!(docs.entry, None);
+        assert_eq!(docs.kind, PathType::Dir);
+        assert_eq!(docs.newest_commit().unwrap().revision, 324641);
+
+        assert_eq!(tree.root().total_size(), 2029056 + 47 + 15987 + 738);
+        assert_eq!(tree.root().newest_commit().unwrap().revision, 324641);
+    }
+
+    #[test]
+    fn walk() {
+        let list = SvnList::parse(LIST_RECURSIVE).unwrap();
+        let tree = RemoteTree::new(&list);
+        let depth_first: Vec<_> = tree.iter_depth_first().map(|(p, _)| p).collect();
+        assert_eq!(
+            depth_first,
+            vec![
+                "assets",
+                "assets/reels",
+                "assets/reels/reels.png",
+                "assets/symbols.bin",
+                "docs",
+                "docs/readme.txt",
+                "manifest",
+            ]
+        );
+        let breadth_first: Vec<_> = tree.iter_breadth_first().map(|(p, _)| p).collect();
+        assert_eq!(
+            breadth_first,
+            vec![
+                "assets",
+                "docs",
+                "manifest",
+                "assets/reels",
+                "assets/symbols.bin",
+                "docs/readme.txt",
+                "assets/reels/reels.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let list = SvnList::parse(LIST_RECURSIVE).unwrap();
+        let tree = RemoteTree::new(&list);
+        assert_eq!(
+            tree.to_string(),
+            r"https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source
+├── assets
+│   ├── reels
+│   │   └── reels.png
+│   └── symbols.bin
+├── docs
+│   └── readme.txt
+└── manifest
+"
+        );
+    }
+
+    const LIST_RECURSIVE: &str = r##"
+<?xml version="1.0" encoding="UTF-8"?>
+<lists>
+<list
+   path="https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source">
+<entry
+   kind="dir">
+<name>assets</name>
+<commit
+   revision="322279">
+<author>rajput</author>
+<date>2021-07-19T07:01:05.938601Z</date>
+</commit>
+</entry>
+<entry
+   kind="dir">
+<name>assets/reels</name>
+<commit
+   revision="301001">
+<author>rajput</author>
+<date>2020-10-09T05:40:54.158765Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>assets/reels/reels.png</name>
+<size>47</size>
+<commit
+   revision="301001">
+<author>rajput</author>
+<date>2020-10-09T05:40:54.158765Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>assets/symbols.bin</name>
+<size>2029056</size>
+<commit
+   revision="313388">
+<author>rajput</author>
+<date>2021-03-09T07:31:23.286485Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>docs/readme.txt</name>
+<size>738</size>
+<commit
+   revision="324641">
+<author>rs102580</author>
+<date>2021-08-20T10:46:56.484066Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>manifest</name>
+<size>15987</size>
+<commit
+   revision="298675">
+<author>rajput</author>
+<date>2020-09-16T06:28:02.597638Z</date>
+</commit>
+</entry>
+</list>
+</lists>
+    "##;
+}
@@ -0,0 +1,272 @@
+use super::info::EntryCommit;
+use crate::{
+    types::{RevisionType, ToCmdArgList},
+    SvnError,
+};
+use log::error;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// options of svn-blame command
+#[derive(Debug, Clone, Default)]
+pub struct BlameOptions {
+    /// `--revision START:END`, blame the file as of END, changes before START are
+    /// reported as START
+    pub revision: Option<(RevisionType, RevisionType)>,
+    /// `--use-merge-history`, report the revision a merged line comes from
+    pub use_merge_history: bool,
+    /// `-x -w`, a change in whitespace only doesn't count
+    pub ignore_whitespace: bool,
+    /// `-x --ignore-eol-style`, a change in line endings only doesn't count
+    pub ignore_eol_style: bool,
+}
+
+impl ToCmdArgList for BlameOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some((start, end)) = self.revision {
+            args.push("--revision".to_owned());
+            args.push(format!("{start}:{end}"));
+        }
+        if self.use_merge_history {
+            args.push("--use-merge-history".to_owned());
+        }
+        let mut extensions = Vec::new();
+        if self.ignore_whitespace {
+            extensions.push("-w");
+        }
+        if self.ignore_eol_style {
+            extensions.push("--ignore-eol-style");
+        }
+        if !extensions.is_empty() {
+            args.push("--extensions".to_owned());
+            args.push(extensions.join(" "));
+        }
+        args
+    }
+}
+
+/// svn blame of one file
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct SvnBlame {
+    /// path or url of the file
+    #[serde(rename(deserialize = "@path"))]
+    pub path: String,
+    /// one entry per line of the file
+    #[serde(rename(deserialize = "entry"), default)]
+    pub lines: Vec<BlameLine>,
+}
+
+/// one line of the file
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct BlameLine {
+    /// line number, from 1
+    #[serde(rename(deserialize = "@line-number"))]
+    pub line_number: usize,
+    /// last change of the line, `None` for a line changed in the working copy
+    pub commit: Option<EntryCommit>,
+    /// with `use_merge_history`, the change merged in by `commit`
+    pub merged: Option<BlameMerged>,
+    /// text of the line, filled from the file content
+    #[serde(skip)]
+    pub text: String,
+}
+
+/// origin of a merged line
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct BlameMerged {
+    /// repository path the line was merged from
+    #[serde(rename(deserialize = "@path"))]
+    pub path: String,
+    /// change in the merge source
+    pub commit: EntryCommit,
+}
+
+impl BlameLine {
+    /// change the line comes from, the merged one when known
+    pub fn origin(&self) -> Option<&EntryCommit> {
+        self.merged
+            .as_ref()
+            .map(|m| &m.commit)
+            .or(self.commit.as_ref())
+    }
+}
+
+#[derive(Deserialize)]
+struct BlameXml {
+    #[serde(default)]
+    target: Vec<SvnBlame>,
+}
+
+impl SvnBlame {
+    /// parse XML text, the first target
+    pub(crate) fn parse(xml_text: &str) -> Result<Self, SvnError> {
+        let blame = quick_xml::de::from_str::<BlameXml>(xml_text.trim()).map_err(|e| {
+            error!("quick_xml parsing error '{e:?}'");
+            SvnError::Deserializer(e)
+        })?;
+        Ok(blame.target.into_iter().next().unwrap_or_default())
+    }
+
+    /// fill the text of the lines from the content of the file, which must have as many
+    /// lines as svn blamed
+    pub(crate) fn with_content(mut self, content: &str) -> Result<Self, SvnError> {
+        let texts = split_lines(content);
+        if texts.len() != self.lines.len() {
+            return Err(SvnError::Other(format!(
+                "'{}' has {} lines, blame has {}",
+                self.path,
+                texts.len(),
+                self.lines.len()
+            )));
+        }
+        for (line, text) in self.lines.iter_mut().zip(texts) {
+            line.text = text.to_owned();
+        }
+        Ok(self)
+    }
+
+    /// number of lines per author of their origin, most lines first, lines changed in the
+    /// working copy aren't counted
+    pub fn ownership(&self) -> Vec<(&str, usize)> {
+        let mut by_author: HashMap<&str, usize> = HashMap::new();
+        for commit in self.lines.iter().filter_map(BlameLine::origin) {
+            *by_author.entry(commit.author.as_str()).or_default() += 1;
+        }
+        let mut ownership: Vec<_> = by_author.into_iter().collect();
+        ownership.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        ownership
+    }
+}
+
+// lines ended by "\n", "\r\n" or a lone "\r" like svn blame counts them
+fn split_lines(content: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut rest = content;
+    while let Some(end) = rest.find(['\n', '\r']) {
+        lines.push(&rest[..end]);
+        let eol = if rest[end..].starts_with("\r\n") {
+            2
+        } else {
+            1
+        };
+        rest = &rest[end + eol..];
+    }
+    if !rest.is_empty() {
+        lines.push(rest);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blame_lines() {
+        let blame = SvnBlame::parse(BLAME)
+            .unwrap()
+            .with_content(CONTENT)
+            .unwrap();
+        assert_eq!(blame.path, "source/main.cpp");
+        assert_eq!(blame.lines.len(), 4);
+        let line = &blame.lines[1];
+        assert_eq!(line.line_number, 2);
+        assert_eq!(line.text, "int main() {");
+        assert_eq!(line.commit.as_ref().unwrap().revision, 310568);
+        let merged = line.merged.as_ref().unwrap();
+        assert_eq!(merged.path, "/GDK_games/Peacock/trunk/source/main.cpp");
+        assert_eq!(line.origin().unwrap().author, "sn100722");
+        assert!(blame.lines[3].commit.is_none());
+        assert_eq!(blame.lines[3].text, "}");
+        assert_eq!(blame.ownership(), vec![("rs102580", 2), ("sn100722", 1)]);
+
+        // classic Mac line endings and no newline at the end of the file
+        let blame = SvnBlame::parse(BLAME)
+            .unwrap()
+            .with_content("#include <cstdio>\rint main() {\r    return 0;\r}")
+            .unwrap();
+        assert_eq!(blame.lines[2].text, "    return 0;");
+        assert_eq!(blame.lines[3].text, "}");
+
+        // the file changed since the blamed revision
+        let changed = "#include <cstdio>\nint main() {\n}\n";
+        assert!(SvnBlame::parse(BLAME)
+            .unwrap()
+            .with_content(changed)
+            .is_err());
+    }
+
+    #[test]
+    fn split_on_every_eol() {
+        assert_eq!(split_lines("a\r\nb\rc\n\nd"), vec!["a", "b", "c", "", "d"]);
+        assert_eq!(split_lines("a\n"), vec!["a"]);
+        assert!(split_lines("").is_empty());
+    }
+
+    #[test]
+    fn blame_options_args() {
+        let options = BlameOptions {
+            revision: Some((RevisionType::Revision(1), RevisionType::Head)),
+            use_merge_history: true,
+            ignore_whitespace: true,
+            ignore_eol_style: true,
+        };
+        assert_eq!(
+            options.to_cmd_arg_list(),
+            vec![
+                "--revision",
+                "1:HEAD",
+                "--use-merge-history",
+                "--extensions",
+                "-w --ignore-eol-style"
+            ]
+        );
+        assert!(BlameOptions::default().to_cmd_arg_list().is_empty());
+    }
+
+    const CONTENT: &str = "#include <cstdio>\r\nint main() {\r\n    return 0;\r\n}\r\n";
+
+    const BLAME: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<blame>
+<target
+   path="source/main.cpp">
+<entry
+   line-number="1">
+<commit
+   revision="300100">
+<author>rs102580</author>
+<date>2021-06-01T08:12:40.102314Z</date>
+</commit>
+</entry>
+<entry
+   line-number="2">
+<commit
+   revision="310568">
+<author>rs102580</author>
+<date>2021-08-20T10:46:56.484066Z</date>
+</commit>
+<merged
+   path="/GDK_games/Peacock/trunk/source/main.cpp">
+<commit
+   revision="310001">
+<author>sn100722</author>
+<date>2021-08-02T14:01:11.000000Z</date>
+</commit>
+</merged>
+</entry>
+<entry
+   line-number="3">
+<commit
+   revision="300100">
+<author>rs102580</author>
+<date>2021-06-01T08:12:40.102314Z</date>
+</commit>
+</entry>
+<entry
+   line-number="4">
+</entry>
+</target>
+</blame>
+"##;
+}
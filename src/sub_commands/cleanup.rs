@@ -0,0 +1,79 @@
+use crate::types::ToCmdArgList;
+
+/// options of svn-cleanup command
+#[derive(Debug, Clone, Default)]
+pub struct CleanupOptions {
+    /// `--remove-unversioned`, delete the unversioned files, needs svn 1.9+
+    pub remove_unversioned: bool,
+    /// `--remove-ignored`, delete the ignored files, needs svn 1.9+
+    pub remove_ignored: bool,
+    /// `--vacuum-pristines`, delete the pristine copies no longer in use, needs svn 1.9+
+    pub vacuum_pristines: bool,
+    /// `--include-externals`, clean up the externals too, needs svn 1.9+
+    pub include_externals: bool,
+}
+
+impl CleanupOptions {
+    /// svn version the options need, `None` for any
+    pub(crate) fn min_version(&self) -> Option<(u64, u64)> {
+        if self.remove_unversioned
+            || self.remove_ignored
+            || self.vacuum_pristines
+            || self.include_externals
+        {
+            Some((1, 9))
+        } else {
+            None
+        }
+    }
+}
+
+impl ToCmdArgList for CleanupOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.remove_unversioned {
+            args.push("--remove-unversioned".to_owned());
+        }
+        if self.remove_ignored {
+            args.push("--remove-ignored".to_owned());
+        }
+        if self.vacuum_pristines {
+            args.push("--vacuum-pristines".to_owned());
+        }
+        if self.include_externals {
+            args.push("--include-externals".to_owned());
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleanup_options_args() {
+        let options = CleanupOptions {
+            remove_unversioned: true,
+            remove_ignored: true,
+            vacuum_pristines: false,
+            include_externals: true,
+        };
+        assert_eq!(
+            options.to_cmd_arg_list(),
+            vec![
+                "--remove-unversioned",
+                "--remove-ignored",
+                "--include-externals"
+            ]
+        );
+        assert_eq!(options.min_version(), Some((1, 9)));
+        let vacuum = CleanupOptions {
+            vacuum_pristines: true,
+            ..Default::default()
+        };
+        assert_eq!(vacuum.min_version(), Some((1, 9)));
+        assert_eq!(CleanupOptions::default().min_version(), None);
+        assert!(CleanupOptions::default().to_cmd_arg_list().is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    cmd_wrapper::{stderr_error, CmdOutput},
+    types::{Depth, ToCmdArgList},
+    SvnError,
+};
+use log::trace;
+
+/// options of svn-commit command
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--changelist ARG`, only commit the paths in these changelists
+    pub changelists: Vec<String>,
+    /// `--keep-changelists`, the committed paths stay in their changelists
+    pub keep_changelists: bool,
+}
+
+impl ToCmdArgList for CommitOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        for changelist in &self.changelists {
+            args.push("--changelist".to_owned());
+            args.push(changelist.clone());
+        }
+        if self.keep_changelists {
+            args.push("--keep-changelists".to_owned());
+        }
+        args
+    }
+}
+
+/// new revision of `svn commit`, reported even when svn printed an error after the commit,
+/// e.g. a failed post-commit hook, `None` when nothing was changed
+pub(crate) fn commit_result(out: CmdOutput) -> Result<Option<u64>, SvnError> {
+    match committed_revision(&out.stdout) {
+        Some(revision) => {
+            if !out.stderr.is_empty() {
+                trace!("r{revision} committed with: {}", out.stderr);
+            }
+            Ok(Some(revision))
+        }
+        None if out.success && out.stderr.is_empty() => Ok(None),
+        None if out.stderr.is_empty() => Err(SvnError::Other(
+            "svn commit exited with an error without printing anything".to_owned(),
+        )),
+        None => Err(stderr_error(out.stderr)),
+    }
+}
+
+fn committed_revision(out: &str) -> Option<u64> {
+    out.lines()
+        .filter_map(|l| l.trim().strip_prefix("Committed revision "))
+        .find_map(|r| r.trim_end_matches('.').parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_output() {
+        assert_eq!(committed_revision(COMMIT), Some(324642));
+        assert_eq!(committed_revision(""), None);
+    }
+
+    #[test]
+    fn commit_with_hook_warning() {
+        let out = CmdOutput {
+            success: false,
+            stdout: COMMIT.to_owned(),
+            stderr: POST_COMMIT_FAILED.to_owned(),
+        };
+        assert_eq!(commit_result(out).unwrap(), Some(324642));
+        let out = CmdOutput {
+            success: true,
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        assert_eq!(commit_result(out).unwrap(), None);
+        let out = CmdOutput {
+            success: false,
+            stdout: "Sending        source/main.cpp\n".to_owned(),
+            stderr: "svn: E155011: File 'C:\\wc\\source\\main.cpp' is out of date\n".to_owned(),
+        };
+        assert!(commit_result(out).is_err());
+        let out = CmdOutput {
+            success: false,
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        assert!(commit_result(out)
+            .unwrap_err()
+            .to_string()
+            .contains("without printing anything"));
+    }
+
+    const COMMIT: &str = "Sending        Games\\src\\Reels.cpp
+Sending        Games\\src\\Reels.h
+Transmitting file data ..done
+Committing transaction...
+Committed revision 324642.
+";
+
+    const POST_COMMIT_FAILED: &str =
+        "svn: warning: W165001: post-commit hook failed (exit code 1) with output:
+mail: cannot send message: Process exited with a non-zero status
+";
+}
@@ -0,0 +1,31 @@
+use crate::types::{Depth, RevisionType, ToCmdArgList};
+
+/// options of svn-diff command
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--changelist ARG`, only diff the paths in these changelists
+    pub changelists: Vec<String>,
+    /// `--revision START:END`, the working copy against BASE when not set
+    pub revision: Option<(RevisionType, RevisionType)>,
+}
+
+impl ToCmdArgList for DiffOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        for changelist in &self.changelists {
+            args.push("--changelist".to_owned());
+            args.push(changelist.clone());
+        }
+        if let Some((start, end)) = self.revision {
+            args.push("--revision".to_owned());
+            args.push(format!("{start}:{end}"));
+        }
+        args
+    }
+}
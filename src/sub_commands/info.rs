@@ -1,21 +1,46 @@
 use crate::errors::SvnError;
-use crate::types::PathType;
+use crate::types::{Depth, PathType, ToCmdArgList};
 use log::trace;
 use serde::{
     de::{self, Deserializer},
     Deserialize,
 };
+use std::path::PathBuf;
+
+/// options of svn-info command
+#[derive(Debug, Clone, Default)]
+pub struct InfoOptions {
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--recursive`, same as `--depth infinity`
+    pub recursive: bool,
+}
+
+impl ToCmdArgList for InfoOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        if self.recursive {
+            args.push("--recursive".to_owned());
+        }
+        args
+    }
+}
 
 /// Return value of SvnCmd . info()
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct SvnInfo {
-    /// entry field from svn xml
-    pub entry: InfoEntry,
+    /// entry fields from svn xml, one per reported path
+    #[serde(rename(deserialize = "entry"), default)]
+    pub entries: Vec<InfoEntry>,
 }
 
 impl SvnInfo {
     pub(crate) fn parse(xml: &str) -> Result<Self, SvnError> {
-        match serde_xml_rs::from_str::<SvnInfo>(xml.trim()) {
+        match quick_xml::de::from_str::<SvnInfo>(xml.trim()) {
             Ok(v) => {
                 trace!("{:?}", v);
                 Ok(v)
@@ -23,29 +48,204 @@ impl SvnInfo {
             Err(e) => Err(SvnError::Deserializer(e)),
         }
     }
+
+    /// first entry, the requested target itself when a single target was passed
+    pub fn entry(&self) -> Option<&InfoEntry> {
+        self.entries.first()
+    }
+
+    /// conflict files of every conflicted entry
+    pub fn conflicts(&self) -> Vec<ConflictFiles> {
+        self.entries
+            .iter()
+            .filter_map(InfoEntry::conflict_files)
+            .collect()
+    }
 }
 
+/// single value of `svn info --show-item ARG`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InfoItem {
+    /// working revision, or the peg revision for urls
+    Revision,
+    /// revision of the last change
+    LastChangedRevision,
+    /// author of the last change
+    LastChangedAuthor,
+    /// date of the last change
+    LastChangedDate,
+    /// url of the item
+    Url,
+    /// url relative to the repository root
+    RelativeUrl,
+    /// repository root url
+    ReposRootUrl,
+    /// repository uuid
+    ReposUuid,
+    /// working copy root path
+    WcRoot,
+}
+
+impl InfoItem {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Revision => "revision",
+            Self::LastChangedRevision => "last-changed-revision",
+            Self::LastChangedAuthor => "last-changed-author",
+            Self::LastChangedDate => "last-changed-date",
+            Self::Url => "url",
+            Self::RelativeUrl => "relative-url",
+            Self::ReposRootUrl => "repos-root-url",
+            Self::ReposUuid => "repos-uuid",
+            Self::WcRoot => "wc-root",
+        }
+    }
+
+    /// pick the same value out of a parsed info entry, used on clients older than 1.9
+    pub(crate) fn value_in(&self, entry: &InfoEntry) -> Option<String> {
+        match self {
+            Self::Revision => entry.revision.map(|r| r.to_string()),
+            Self::LastChangedRevision => entry.commit.as_ref().map(|c| c.revision.to_string()),
+            Self::LastChangedAuthor => entry.commit.as_ref().map(|c| c.author.clone()),
+            Self::LastChangedDate => entry.commit.as_ref().map(|c| c.date.clone()),
+            Self::Url => Some(entry.url.clone()),
+            Self::RelativeUrl => Some(entry.relative_url.clone()),
+            Self::ReposRootUrl => Some(entry.repository.root.clone()),
+            Self::ReposUuid => Some(entry.repository.uuid.clone()),
+            Self::WcRoot => entry
+                .wc_info
+                .as_ref()
+                .and_then(|w| w.wcroot_abspath.clone()),
+        }
+    }
+}
+
+/// info about one path
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct InfoEntry {
-    #[serde(deserialize_with = "to_pathtype")]
+    /// path as reported by svn, relative to the current dir or the last component of a url
+    #[serde(rename(deserialize = "@path"))]
+    pub path: String,
+    /// working revision, or the peg revision for urls
+    #[serde(
+        rename(deserialize = "@revision"),
+        deserialize_with = "to_revision",
+        default
+    )]
+    pub revision: Option<u32>,
+    /// is file or dir
+    #[serde(rename(deserialize = "@kind"), deserialize_with = "to_pathtype")]
     pub kind: PathType,
+    /// url of the item
     pub url: String,
+    /// url relative to the repository root, '^/...'
     #[serde(rename(deserialize = "relative-url"))]
     pub relative_url: String,
+    /// repository details
     pub repository: EntryRepository,
-    pub commit: EntryCommit,
+    /// working copy details, absent for urls
+    #[serde(rename(deserialize = "wc-info"))]
+    pub wc_info: Option<WcInfo>,
+    /// last commit, absent for items scheduled for addition
+    pub commit: Option<EntryCommit>,
+    /// lock held on the item
+    pub lock: Option<EntryLock>,
+    /// text and property conflicts on the item
+    #[serde(rename(deserialize = "conflict"), default)]
+    pub conflicts: Vec<Conflict>,
+    /// tree conflict on the item
+    #[serde(rename(deserialize = "tree-conflict"))]
+    pub tree_conflict: Option<TreeConflict>,
 }
 
+impl InfoEntry {
+    /// files svn left next to the entry for its conflicts, `None` when not conflicted
+    pub fn conflict_files(&self) -> Option<ConflictFiles> {
+        if self.conflicts.is_empty() && self.tree_conflict.is_none() {
+            return None;
+        }
+        let mut files = ConflictFiles {
+            path: PathBuf::from(&self.path),
+            mine: None,
+            old: None,
+            new: None,
+            prop_reject: None,
+            tree_conflict: self.tree_conflict.clone(),
+        };
+        for conflict in &self.conflicts {
+            let path = |file: &Option<String>| file.as_ref().map(PathBuf::from);
+            files.mine = files.mine.or(path(&conflict.prev_wc_file));
+            files.old = files.old.or(path(&conflict.prev_base_file));
+            files.new = files.new.or(path(&conflict.cur_base_file));
+            files.prop_reject = files.prop_reject.or(path(&conflict.prop_file));
+        }
+        Some(files)
+    }
+}
+
+/// conflicts of one path, with the files svn left next to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictFiles {
+    /// conflicted path
+    pub path: PathBuf,
+    /// working file before the operation, the '.mine' file
+    pub mine: Option<PathBuf>,
+    /// base file before the operation, the '.rOLD' file
+    pub old: Option<PathBuf>,
+    /// incoming base file, the '.rNEW' file
+    pub new: Option<PathBuf>,
+    /// property reject file, the '.prej' file
+    pub prop_reject: Option<PathBuf>,
+    /// tree conflict on the path
+    pub tree_conflict: Option<TreeConflict>,
+}
+
+/// repository details
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct EntryRepository {
+    /// repository root url
     pub root: String,
+    /// repository uuid
     pub uuid: String,
 }
 
+/// `<wc-info>` element of an entry
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WcInfo {
+    /// absolute path of the working copy root
+    #[serde(rename(deserialize = "wcroot-abspath"))]
+    pub wcroot_abspath: Option<String>,
+    /// scheduled operation, one of normal, add, delete or replace
+    pub schedule: Option<String>,
+    /// depth of the item, `None` for 'exclude' and unknown depths
+    #[serde(deserialize_with = "to_depth", default)]
+    pub depth: Option<Depth>,
+    /// url the item was copied from
+    #[serde(rename(deserialize = "copy-from-url"))]
+    pub copy_from_url: Option<String>,
+    /// revision the item was copied from
+    #[serde(rename(deserialize = "copy-from-rev"))]
+    pub copy_from_rev: Option<u32>,
+    /// last time the text was updated
+    #[serde(rename(deserialize = "text-updated"))]
+    pub text_updated: Option<String>,
+    /// checksum of the pristine text
+    pub checksum: Option<String>,
+    /// changelist the item belongs to
+    pub changelist: Option<String>,
+    /// path the item was moved here from
+    #[serde(rename(deserialize = "moved-from"))]
+    pub moved_from: Option<String>,
+    /// path the item was moved away to
+    #[serde(rename(deserialize = "moved-to"))]
+    pub moved_to: Option<String>,
+}
+
 /// commit entry
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct EntryCommit {
     /// revision number
+    #[serde(rename(deserialize = "@revision"))]
     pub revision: u32,
     /// author name
     pub author: String,
@@ -53,6 +253,108 @@ pub struct EntryCommit {
     pub date: String,
 }
 
+/// lock held on a path in the repository or in the working copy
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EntryLock {
+    /// lock token
+    pub token: String,
+    /// user who holds the lock
+    pub owner: String,
+    /// lock comment
+    pub comment: Option<String>,
+    /// date the lock was created
+    pub created: String,
+    /// date the lock expires, if ever
+    pub expires: Option<String>,
+}
+
+/// `<conflict>` element of an entry, a text or property conflict
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Conflict {
+    /// 'text' or 'property', absent on svn older than 1.8
+    #[serde(rename(deserialize = "@type"))]
+    pub conflict_type: Option<String>,
+    /// operation which raised the conflict: update, switch or merge
+    #[serde(rename(deserialize = "@operation"))]
+    pub operation: Option<String>,
+    /// versions involved in the conflict
+    #[serde(rename(deserialize = "version"), default)]
+    pub versions: Vec<ConflictVersion>,
+    /// base file before the operation, the '.rOLD' file
+    #[serde(rename(deserialize = "prev-base-file"))]
+    pub prev_base_file: Option<String>,
+    /// working file before the operation, the '.mine' file
+    #[serde(rename(deserialize = "prev-wc-file"))]
+    pub prev_wc_file: Option<String>,
+    /// incoming base file, the '.rNEW' file
+    #[serde(rename(deserialize = "cur-base-file"))]
+    pub cur_base_file: Option<String>,
+    /// property reject file, the '.prej' file
+    #[serde(rename(deserialize = "prop-file"))]
+    pub prop_file: Option<String>,
+}
+
+/// `<tree-conflict>` element of an entry
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TreeConflict {
+    /// name of the conflicted item
+    #[serde(rename(deserialize = "@victim"))]
+    pub victim: String,
+    /// node kind of the victim
+    #[serde(rename(deserialize = "@kind"))]
+    pub kind: String,
+    /// operation which raised the conflict: update, switch or merge
+    #[serde(rename(deserialize = "@operation"))]
+    pub operation: String,
+    /// incoming change: edit, add, delete or replace
+    #[serde(rename(deserialize = "@action"))]
+    pub action: String,
+    /// local change: edit, obstruction, delete, missing, unversioned, add, replace,
+    /// moved-away or moved-here
+    #[serde(rename(deserialize = "@reason"))]
+    pub reason: String,
+    /// versions involved in the conflict
+    #[serde(rename(deserialize = "version"), default)]
+    pub versions: Vec<ConflictVersion>,
+}
+
+impl TreeConflict {
+    /// description as printed by svn, e.g. 'local file moved here, incoming file edit upon update'
+    pub fn description(&self) -> String {
+        format!(
+            "local {kind} {}, incoming {kind} {} upon {}",
+            self.reason.replace('-', " "),
+            self.action.replace('-', " "),
+            self.operation,
+            kind = self.kind,
+        )
+    }
+}
+
+/// `<version>` element of a conflict
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ConflictVersion {
+    /// source-left or source-right
+    #[serde(rename(deserialize = "@side"))]
+    pub side: String,
+    /// node kind at this version
+    #[serde(rename(deserialize = "@kind"))]
+    pub kind: Option<String>,
+    /// path inside the repository
+    #[serde(rename(deserialize = "@path-in-repos"))]
+    pub path_in_repos: Option<String>,
+    /// repository root url
+    #[serde(rename(deserialize = "@repos-url"))]
+    pub repos_url: Option<String>,
+    /// revision of this version
+    #[serde(
+        rename(deserialize = "@revision"),
+        deserialize_with = "to_revision",
+        default
+    )]
+    pub revision: Option<u32>,
+}
+
 pub(crate) fn to_pathtype<'de, D>(deserializer: D) -> Result<PathType, D::Error>
 where
     D: Deserializer<'de>,
@@ -67,6 +369,23 @@ where
     }
 }
 
+// svn writes a sentence instead of the number for unversioned items
+fn to_revision<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(s.parse::<u32>().ok())
+}
+
+fn to_depth<'de, D>(deserializer: D) -> Result<Option<Depth>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(Depth::parse(&s))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,7 +416,9 @@ mod tests {
         let info = SvnInfo::parse(XML).unwrap();
         println!("{:#?}", info);
         assert_eq!(info, SvnInfo {
-                entry: InfoEntry {
+                entries: vec![InfoEntry {
+                    path: ".".to_owned(),
+                    revision: Some(324270),
                     kind: PathType::Dir,
                     url: "https://svn.ali.global/GDK_games/GDK_games/BLS/HHR/BuffaloDeluxe/trunk/source".to_owned(),
                     relative_url: "^/GDK_games/BLS/HHR/BuffaloDeluxe/trunk/source".to_owned(),
@@ -105,13 +426,271 @@ mod tests {
                         root: "https://svn.ali.global/GDK_games".to_owned(),
                         uuid: "e0c53376-34c8-4e4f-a567-4bb579746d60".to_owned(),
                     },
-                    commit: EntryCommit {
+                    wc_info: Some(WcInfo {
+                        wcroot_abspath: Some("C:/Users/rajput/R/svn/nAble/FeatureDevelopment/Monaco/TXM-603_HHR/2.02/trunk/Runtime/core/Games/BuffaloDeluxe".to_owned()),
+                        schedule: Some("normal".to_owned()),
+                        depth: Some(Depth::Infinity),
+                        copy_from_url: None,
+                        copy_from_rev: None,
+                        text_updated: None,
+                        checksum: None,
+                        changelist: None,
+                        moved_from: None,
+                        moved_to: None,
+                    }),
+                    commit: Some(EntryCommit {
                         revision: 324270,
                         author: "rajput".to_owned(),
                         date: "2021-08-16T15:02:49.091280Z".to_owned(),
-                    },
-                },
+                    }),
+                    lock: None,
+                    conflicts: vec![],
+                    tree_conflict: None,
+                }],
             },
         );
     }
+
+    #[test]
+    fn multiple_entries_with_lock_and_conflicts() {
+        let info = SvnInfo::parse(INFO_CONFLICTS).unwrap();
+        assert_eq!(info.entries.len(), 4);
+
+        let reels = &info.entries[0];
+        assert_eq!(reels.path, "assets\\reels.png");
+        let wc_info = reels.wc_info.as_ref().unwrap();
+        assert_eq!(
+            wc_info.checksum.as_deref(),
+            Some("1c2f5b9e0d8e4a3f6b7c8d9e0f1a2b3c4d5e6f70")
+        );
+        assert_eq!(wc_info.changelist.as_deref(), Some("art-drop"));
+        assert_eq!(reels.lock.as_ref().unwrap().owner, "goela");
+        assert_eq!(
+            reels.lock.as_ref().unwrap().comment.as_deref(),
+            Some("reworking reel art")
+        );
+
+        let game = &info.entries[1];
+        assert_eq!(game.conflicts.len(), 1);
+        let conflict = &game.conflicts[0];
+        assert_eq!(conflict.conflict_type.as_deref(), Some("text"));
+        assert_eq!(conflict.operation.as_deref(), Some("update"));
+        assert_eq!(conflict.versions.len(), 2);
+        assert_eq!(conflict.versions[1].revision, Some(324700));
+        assert_eq!(
+            conflict.prev_wc_file.as_deref(),
+            Some("C:\\wc\\src\\Game.cpp.mine")
+        );
+        assert_eq!(
+            conflict.prev_base_file.as_deref(),
+            Some("C:\\wc\\src\\Game.cpp.r324690")
+        );
+        assert_eq!(
+            conflict.cur_base_file.as_deref(),
+            Some("C:\\wc\\src\\Game.cpp.r324700")
+        );
+
+        let copied = &info.entries[2];
+        assert_eq!(copied.commit, None);
+        let wc_info = copied.wc_info.as_ref().unwrap();
+        assert_eq!(wc_info.schedule.as_deref(), Some("add"));
+        assert_eq!(wc_info.copy_from_rev, Some(324500));
+        assert_eq!(
+            wc_info.moved_from.as_deref(),
+            Some("C:/wc/src/OldPayout.cpp")
+        );
+        let tree_conflict = copied.tree_conflict.as_ref().unwrap();
+        assert_eq!(tree_conflict.victim, "Payout.cpp");
+        assert_eq!(tree_conflict.action, "edit");
+        assert_eq!(tree_conflict.reason, "moved-here");
+        assert_eq!(tree_conflict.versions[0].side, "source-left");
+        assert_eq!(
+            tree_conflict.description(),
+            "local file moved here, incoming file edit upon update"
+        );
+
+        let added = &info.entries[3];
+        assert_eq!(added.revision, None);
+        assert_eq!(added.wc_info.as_ref().unwrap().depth, None);
+    }
+
+    #[test]
+    fn conflict_inspector() {
+        let info = SvnInfo::parse(INFO_CONFLICTS).unwrap();
+        let conflicts = info.conflicts();
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].path, PathBuf::from("src\\Game.cpp"));
+        assert_eq!(
+            conflicts[0].mine,
+            Some(PathBuf::from("C:\\wc\\src\\Game.cpp.mine"))
+        );
+        assert_eq!(
+            conflicts[0].old,
+            Some(PathBuf::from("C:\\wc\\src\\Game.cpp.r324690"))
+        );
+        assert_eq!(
+            conflicts[0].new,
+            Some(PathBuf::from("C:\\wc\\src\\Game.cpp.r324700"))
+        );
+        assert!(conflicts[0].tree_conflict.is_none());
+        assert_eq!(conflicts[1].mine, None);
+        assert_eq!(
+            conflicts[1].tree_conflict.as_ref().unwrap().victim,
+            "Payout.cpp"
+        );
+    }
+
+    #[test]
+    fn show_item_fallback() {
+        let info = SvnInfo::parse(INFO_CONFLICTS).unwrap();
+        let reels = &info.entries[0];
+        assert_eq!(
+            InfoItem::Revision.value_in(reels).as_deref(),
+            Some("324690")
+        );
+        assert_eq!(
+            InfoItem::LastChangedRevision.value_in(reels).as_deref(),
+            Some("324549")
+        );
+        assert_eq!(
+            InfoItem::LastChangedAuthor.value_in(reels).as_deref(),
+            Some("goela")
+        );
+        assert_eq!(
+            InfoItem::ReposRootUrl.value_in(reels).as_deref(),
+            Some("https://svn.ali.global/GDK_games")
+        );
+        assert_eq!(InfoItem::WcRoot.value_in(reels).as_deref(), Some("C:/wc"));
+        let added = &info.entries[3];
+        assert_eq!(InfoItem::Revision.value_in(added), None);
+        assert_eq!(InfoItem::LastChangedDate.value_in(added), None);
+    }
+
+    const INFO_CONFLICTS: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<info>
+<entry
+   path="assets\reels.png"
+   revision="324690"
+   kind="file">
+<url>https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/assets/reels.png</url>
+<relative-url>^/GDK_games/BLS/NYL/trunk/source/assets/reels.png</relative-url>
+<repository>
+<root>https://svn.ali.global/GDK_games</root>
+<uuid>e0c53376-34c8-4e4f-a567-4bb579746d60</uuid>
+</repository>
+<wc-info>
+<wcroot-abspath>C:/wc</wcroot-abspath>
+<schedule>normal</schedule>
+<depth>infinity</depth>
+<text-updated>2021-08-20T10:50:12.000000Z</text-updated>
+<checksum>1c2f5b9e0d8e4a3f6b7c8d9e0f1a2b3c4d5e6f70</checksum>
+<changelist>art-drop</changelist>
+</wc-info>
+<commit
+   revision="324549">
+<author>goela</author>
+<date>2021-08-19T10:22:54.573528Z</date>
+</commit>
+<lock>
+<token>opaquelocktoken:2bc9a8a6-3f0d-4c4a-9d0b-f0e4c8b2a111</token>
+<owner>goela</owner>
+<comment>reworking reel art</comment>
+<created>2021-08-21T09:12:43.107212Z</created>
+</lock>
+</entry>
+<entry
+   path="src\Game.cpp"
+   revision="324700"
+   kind="file">
+<url>https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/src/Game.cpp</url>
+<relative-url>^/GDK_games/BLS/NYL/trunk/source/src/Game.cpp</relative-url>
+<repository>
+<root>https://svn.ali.global/GDK_games</root>
+<uuid>e0c53376-34c8-4e4f-a567-4bb579746d60</uuid>
+</repository>
+<wc-info>
+<wcroot-abspath>C:/wc</wcroot-abspath>
+<schedule>normal</schedule>
+<depth>infinity</depth>
+</wc-info>
+<commit
+   revision="324641">
+<author>rs102580</author>
+<date>2021-08-20T10:46:56.484066Z</date>
+</commit>
+<conflict
+   type="text"
+   operation="update">
+<version
+   side="source-left"
+   kind="file"
+   path-in-repos="GDK_games/BLS/NYL/trunk/source/src/Game.cpp"
+   repos-url="https://svn.ali.global/GDK_games"
+   revision="324690"/>
+<version
+   side="source-right"
+   kind="file"
+   path-in-repos="GDK_games/BLS/NYL/trunk/source/src/Game.cpp"
+   repos-url="https://svn.ali.global/GDK_games"
+   revision="324700"/>
+<prev-base-file>C:\wc\src\Game.cpp.r324690</prev-base-file>
+<prev-wc-file>C:\wc\src\Game.cpp.mine</prev-wc-file>
+<cur-base-file>C:\wc\src\Game.cpp.r324700</cur-base-file>
+</conflict>
+</entry>
+<entry
+   path="src\Payout.cpp"
+   revision="324700"
+   kind="file">
+<url>https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/src/Payout.cpp</url>
+<relative-url>^/GDK_games/BLS/NYL/trunk/source/src/Payout.cpp</relative-url>
+<repository>
+<root>https://svn.ali.global/GDK_games</root>
+<uuid>e0c53376-34c8-4e4f-a567-4bb579746d60</uuid>
+</repository>
+<wc-info>
+<wcroot-abspath>C:/wc</wcroot-abspath>
+<schedule>add</schedule>
+<depth>infinity</depth>
+<copy-from-url>https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/src/OldPayout.cpp</copy-from-url>
+<copy-from-rev>324500</copy-from-rev>
+<moved-from>C:/wc/src/OldPayout.cpp</moved-from>
+</wc-info>
+<tree-conflict
+   victim="Payout.cpp"
+   kind="file"
+   operation="update"
+   action="edit"
+   reason="moved-here">
+<version
+   side="source-left"
+   kind="file"
+   path-in-repos="GDK_games/BLS/NYL/trunk/source/src/Payout.cpp"
+   repos-url="https://svn.ali.global/GDK_games"
+   revision="324690"/>
+<version
+   side="source-right"
+   kind="file"
+   path-in-repos="GDK_games/BLS/NYL/trunk/source/src/Payout.cpp"
+   repos-url="https://svn.ali.global/GDK_games"
+   revision="324700"/>
+</tree-conflict>
+</entry>
+<entry
+   path="src\New.cpp"
+   revision="Resource is not under version control."
+   kind="file">
+<url>https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/src/New.cpp</url>
+<relative-url>^/GDK_games/BLS/NYL/trunk/source/src/New.cpp</relative-url>
+<repository>
+<root>https://svn.ali.global/GDK_games</root>
+<uuid>e0c53376-34c8-4e4f-a567-4bb579746d60</uuid>
+</repository>
+<wc-info>
+<wcroot-abspath>C:/wc</wcroot-abspath>
+<schedule>add</schedule>
+<depth>exclude</depth>
+</wc-info>
+</entry>
+</info>"##;
 }
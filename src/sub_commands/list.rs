@@ -1,40 +1,112 @@
 use crate::{
-    errors::SvnError, sub_commands::info::to_pathtype, sub_commands::info::EntryCommit,
-    types::PathType,
+    errors::SvnError,
+    sub_commands::info::to_pathtype,
+    sub_commands::info::{EntryCommit, EntryLock},
+    types::{Depth, PathType, RevisionType, ToCmdArgList},
 };
 use log::error;
+use regex::Regex;
 use serde::Deserialize;
 use std::{
     collections::{vec_deque::Iter, HashMap, VecDeque},
     fmt::Display,
-    mem,
-    ops::Deref,
+    slice,
 };
 
+/// options of svn-list command
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--revision ARG`
+    pub revision: Option<RevisionType>,
+    /// peg revision, appended to every target as `TARGET@PEG`
+    pub peg_revision: Option<RevisionType>,
+    /// `--search ARG`, glob patterns matched against the entry names, needs svn 1.10+
+    pub search: Vec<String>,
+    /// `--include-externals`
+    pub include_externals: bool,
+}
+
+impl ListOptions {
+    pub(crate) fn target_arg(&self, target: &str) -> String {
+        match self.peg_revision {
+            Some(peg) => format!("{target}@{peg}"),
+            None => target.to_owned(),
+        }
+    }
+}
+
+impl ToCmdArgList for ListOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        if let Some(revision) = self.revision {
+            args.push("--revision".to_owned());
+            args.push(revision.to_string());
+        }
+        for pattern in &self.search {
+            args.push("--search".to_owned());
+            args.push(pattern.clone());
+        }
+        if self.include_externals {
+            args.push("--include-externals".to_owned());
+        }
+        args
+    }
+}
+
 /// svn list
 #[derive(Deserialize, Debug, Clone, Default)]
 pub struct SvnList {
-    /// the list
-    pub list: Entry,
+    /// one list per `<list>` block, svn prints one block per target
+    #[serde(rename(deserialize = "list"), default)]
+    pub lists: Vec<Entry>,
 }
 
+/// `<list>` block of one target
 #[derive(Deserialize, Debug, Clone, Default)]
 pub struct Entry {
+    /// listed target
+    #[serde(rename(deserialize = "@path"))]
+    pub path: Option<String>,
+    /// entries of the target
+    pub entry: Option<VecDeque<ListEntry>>,
+    /// externals defined below the target, only with `--include-externals`
+    #[serde(rename(deserialize = "external"), default)]
+    pub externals: Vec<ListExternal>,
+}
+
+/// `<external>` block, the entries pulled in by one externals definition
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ListExternal {
+    /// url of the directory which owns the `svn:externals` property
+    #[serde(rename(deserialize = "@parent_url"), alias = "@parent-url")]
+    pub parent_url: String,
+    /// local name of the external, relative to the parent
+    #[serde(rename(deserialize = "@target"))]
+    pub target: String,
+    /// entries of the external
     pub entry: Option<VecDeque<ListEntry>>,
 }
 
 /// on asking create a map from file/dir name to the list_entry
 #[derive(Deserialize, Debug, Clone, Default)]
 pub struct SvnListMap {
-    svn_list: SvnList,
-    map: HashMap<String, usize>,
+    entries: Vec<ListEntry>,
+    // several listed targets may have entries of the same name
+    map: HashMap<String, Vec<usize>>,
+    url_map: HashMap<String, usize>,
 }
 
 /// SvnList is madeup of these entries
 #[derive(Deserialize, PartialEq, Clone, Debug)]
 pub struct ListEntry {
-    #[serde(deserialize_with = "to_pathtype")]
     /// is file or dir
+    #[serde(rename(deserialize = "@kind"), deserialize_with = "to_pathtype")]
     pub kind: PathType,
     /// relative path name
     pub name: Option<String>,
@@ -42,6 +114,8 @@ pub struct ListEntry {
     pub size: Option<usize>,
     /// commit structure
     pub commit: EntryCommit,
+    /// lock held on the entry
+    pub lock: Option<EntryLock>,
 }
 
 impl Display for ListEntry {
@@ -57,62 +131,141 @@ impl Display for ListEntry {
 impl SvnList {
     /// parse XML text
     pub(crate) fn parse(xml_text: &str) -> Result<Self, SvnError> {
-        serde_xml_rs::from_str::<Self>(xml_text.trim()).map_err(|e| {
-            error!("serde_xml_rs parsing error '{e:?}'");
+        quick_xml::de::from_str::<Self>(xml_text.trim()).map_err(|e| {
+            error!("quick_xml parsing error '{e:?}'");
             SvnError::Deserializer(e)
         })
     }
 
-    /// returns iterator
-    pub fn iter_opt(&self) -> Option<ListInspector> {
-        self.list.entry.as_ref().map(|entries| ListInspector {
-            iter: entries.iter(),
+    /// returns iterator over the entries of every `<list>` block, externals aren't included
+    pub fn iter_opt(&self) -> Option<ListInspector<'_>> {
+        if self.lists.iter().all(|l| l.entry.is_none()) {
+            return None;
+        }
+        Some(ListInspector {
+            lists: self.lists.iter(),
+            iter: None,
         })
     }
 
-    /// return SvnListMap
-    pub fn into_list_map(mut self) -> SvnListMap {
-        let map = self
-            .list
-            .entry
-            .as_mut()
-            .map(|entries| {
-                entries
-                    .iter_mut()
-                    .enumerate()
-                    .map(|(i, entry)| {
-                        let mut name = entry.name.take().unwrap();
-                        let name = &mut name; // name will be present as parsed from the svn list xml out
-                        (mem::take(name), i)
-                    })
-                    .collect()
-            })
-            .unwrap_or(HashMap::new());
-        SvnListMap {
-            svn_list: self,
-            map,
+    /// externals of every `<list>` block
+    pub fn externals(&self) -> impl Iterator<Item = &ListExternal> {
+        self.lists.iter().flat_map(|l| l.externals.iter())
+    }
+
+    /// return SvnListMap, entries without a name and externals are left out
+    pub fn into_list_map(self) -> SvnListMap {
+        let mut list_map = SvnListMap::default();
+        for list in self.lists {
+            let list_path = list.path.as_deref().map(|p| p.trim_end_matches('/'));
+            for entry in list.entry.into_iter().flatten() {
+                let Some(name) = entry.name.clone() else {
+                    continue;
+                };
+                let index = list_map.entries.len();
+                if let Some(list_path) = list_path {
+                    list_map
+                        .url_map
+                        .insert(format!("{list_path}/{name}"), index);
+                }
+                list_map.map.entry(name).or_default().push(index);
+                list_map.entries.push(entry);
+            }
         }
+        list_map
     }
 }
 
-impl Deref for SvnListMap {
-    type Target = HashMap<String, usize>;
+impl SvnListMap {
+    /// entry by its name, the path relative to the listed target, the first one listed when
+    /// several targets have it, see `get_all`
+    pub fn get(&self, name: &str) -> Option<&ListEntry> {
+        self.get_all(name).next()
+    }
+
+    /// entries of every listed target having the name, in the order svn listed them
+    pub fn get_all<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a ListEntry> {
+        self.map
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.entries[i])
+    }
+
+    /// entry by its full path, the listed target joined with the entry name,
+    /// that is the full url when an url was listed
+    pub fn get_by_url(&self, url: &str) -> Option<&ListEntry> {
+        self.url_map.get(url).map(|&i| &self.entries[i])
+    }
+
+    /// `(name, entry)` pairs in the order svn listed them
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ListEntry)> {
+        self.entries
+            .iter()
+            .filter_map(|e| e.name.as_deref().map(|name| (name, e)))
+    }
+
+    /// `(name, entry)` pairs whose name starts with the prefix
+    pub fn with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a ListEntry)> {
+        self.iter()
+            .filter(move |(name, _)| name.starts_with(prefix))
+    }
+
+    /// `(name, entry)` pairs whose name matches the glob pattern,
+    /// '*' and '?' don't cross a '/' while '**' matches any number of dirs
+    pub fn glob(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = (&str, &ListEntry)>, SvnError> {
+        let re = glob_to_regex(pattern)?;
+        Ok(self.iter().filter(move |(name, _)| re.is_match(name)))
+    }
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.map
+fn glob_to_regex(pattern: &str) -> Result<Regex, SvnError> {
+    let mut re = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            other => re.push_str(&regex::escape(&other.to_string())),
+        }
     }
+    re.push('$');
+    Regex::new(&re).map_err(|e| SvnError::Other(format!("invalid glob '{pattern}': {e:?}")))
 }
 
+/// iterator over the entries of a `SvnList`
 #[derive(Clone)]
 pub struct ListInspector<'a> {
-    iter: Iter<'a, ListEntry>,
+    lists: slice::Iter<'a, Entry>,
+    iter: Option<Iter<'a, ListEntry>>,
 }
 
 impl<'a> Iterator for ListInspector<'a> {
     type Item = &'a ListEntry;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        loop {
+            if let Some(entry) = self.iter.as_mut().and_then(|iter| iter.next()) {
+                return Some(entry);
+            }
+            let list = self.lists.next()?;
+            self.iter = list.entry.as_ref().map(|entries| entries.iter());
+        }
     }
 }
 
@@ -122,7 +275,7 @@ mod tests {
 
     #[test]
     fn parsing_() {
-        let xml_text: SvnList = serde_xml_rs::from_str(LIST_XML_3.trim()).unwrap();
+        let xml_text: SvnList = quick_xml::de::from_str(LIST_XML_3.trim()).unwrap();
         println!("{:?}", xml_text);
     }
 
@@ -134,6 +287,29 @@ mod tests {
         }
     }
 
+    // 200k entries, 36 MB, `SvnList::parse` took 2.1 s with serde-xml-rs and 0.38 s with
+    // quick-xml, release build, median of 5 parses in one process
+    #[test]
+    #[ignore]
+    fn parse_large_listing() {
+        const ENTRIES: usize = 200_000;
+        let entry =
+            &LIST_XML_1[LIST_XML_1.find("<entry").unwrap()..LIST_XML_1.rfind("</list>").unwrap()];
+        let count = entry.matches("<entry").count();
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<lists>\n<list\n   path=\".\">\n",
+        );
+        for _ in 0..ENTRIES / count {
+            xml.push_str(entry);
+        }
+        xml.push_str("</list>\n</lists>\n");
+        let list = SvnList::parse(&xml).unwrap();
+        let entries: Vec<_> = list.iter_opt().unwrap().collect();
+        assert_eq!(entries.len(), ENTRIES / count * count);
+        assert_eq!(entries[0].name, entries[count].name);
+        assert_eq!(entries[count - 1].name, entries[entries.len() - 1].name);
+    }
+
     #[test]
     fn list_iter() {
         for list_xml in [LIST_XML_1, LIST_XML_2, LIST_XML_3] {
@@ -146,6 +322,223 @@ mod tests {
         }
     }
 
+    #[test]
+    fn multiple_lists_with_locks_and_externals() {
+        let list = SvnList::parse(LIST_XML_EXTERNALS).unwrap();
+        assert_eq!(list.lists.len(), 2);
+        assert_eq!(
+            list.lists[1].path.as_deref(),
+            Some("https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/src")
+        );
+        let names: Vec<_> = list
+            .iter_opt()
+            .unwrap()
+            .map(|e| e.name.clone().unwrap())
+            .collect();
+        assert_eq!(names, vec!["lib", "manifest", "Game.cpp"]);
+        let lock = list.lists[0].entry.as_ref().unwrap()[1]
+            .lock
+            .as_ref()
+            .unwrap();
+        assert_eq!(lock.owner, "goela");
+        assert_eq!(lock.comment.as_deref(), Some("updating paytable"));
+
+        let externals: Vec<_> = list.externals().collect();
+        assert_eq!(externals.len(), 2);
+        assert_eq!(
+            externals[0].parent_url,
+            "https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/lib"
+        );
+        assert_eq!(externals[0].target, "NitroCommon");
+        assert_eq!(externals[0].entry.as_ref().unwrap().len(), 2);
+        assert_eq!(externals[1].target, "NitroParticles");
+        assert_eq!(
+            externals[1].entry.as_ref().unwrap()[0].name.as_deref(),
+            Some("Particles.cpp")
+        );
+    }
+
+    #[test]
+    fn list_map() {
+        let mut list = SvnList::parse(LIST_XML_EXTERNALS).unwrap();
+        // entries without a name must not panic
+        list.lists[1].entry.as_mut().unwrap()[0].name = None;
+        let map = list.into_list_map();
+        assert_eq!(map.iter().count(), 2);
+        let manifest = map.get("manifest").unwrap();
+        assert_eq!(manifest.name.as_deref(), Some("manifest"));
+        assert_eq!(manifest.size, Some(15987));
+        assert_eq!(
+            map.get_by_url(
+                "https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/manifest"
+            ),
+            Some(manifest)
+        );
+        assert!(map.get("Game.cpp").is_none());
+        let names: Vec<_> = map.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["lib", "manifest"]);
+    }
+
+    #[test]
+    fn list_map_same_names() {
+        // the second target has a `manifest` too
+        let mut list = SvnList::parse(LIST_XML_EXTERNALS).unwrap();
+        list.lists[1].entry.as_mut().unwrap()[0].name = Some("manifest".to_owned());
+        let map = list.into_list_map();
+        assert_eq!(map.get("manifest").unwrap().size, Some(15987));
+        let all: Vec<_> = map.get_all("manifest").map(|e| e.size).collect();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0], Some(15987));
+        assert_ne!(all[0], all[1]);
+        assert!(map
+            .get_by_url(
+                "https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/src/manifest"
+            )
+            .is_some());
+        assert_eq!(map.iter().count(), 3);
+        assert_eq!(map.get_all("Game.cpp").count(), 0);
+    }
+
+    #[test]
+    fn list_map_queries() {
+        let map = SvnList::parse(LIST_XML_1).unwrap().into_list_map();
+        let prefixed: Vec<_> = map.with_prefix("BuffaloChief.").map(|(n, _)| n).collect();
+        assert_eq!(
+            prefixed,
+            vec![
+                "BuffaloChief.vcxproj",
+                "BuffaloChief.vcxproj.filters",
+                "BuffaloChief.vcxproj.user"
+            ]
+        );
+        let globbed: Vec<_> = map.glob("*.ps1").unwrap().map(|(n, _)| n).collect();
+        assert_eq!(
+            globbed,
+            vec!["build_all.ps1", "clean_and_run_buffalo_chief.ps1"]
+        );
+        let globbed: Vec<_> = map.glob("?????.txt").unwrap().map(|(n, _)| n).collect();
+        assert_eq!(globbed, vec!["setup.txt"]);
+        let map =
+            SvnList::parse(&LIST_XML_1.replace("<name>lib</name>", "<name>lib/a/b.json</name>"))
+                .unwrap()
+                .into_list_map();
+        assert_eq!(map.glob("*.json").unwrap().count(), 2);
+        assert_eq!(map.glob("**/*.json").unwrap().count(), 3);
+        assert_eq!(map.glob("lib/**").unwrap().count(), 1);
+    }
+
+    #[test]
+    fn list_options_args() {
+        let options = ListOptions {
+            depth: Some(Depth::Infinity),
+            revision: Some(RevisionType::Revision(324700)),
+            peg_revision: Some(RevisionType::Head),
+            search: vec!["*.png".to_owned(), "reel s*".to_owned()],
+            include_externals: true,
+        };
+        assert_eq!(
+            options.to_cmd_arg_list(),
+            vec![
+                "--depth",
+                "infinity",
+                "--revision",
+                "324700",
+                "--search",
+                "*.png",
+                "--search",
+                "reel s*",
+                "--include-externals",
+            ]
+        );
+        assert_eq!(options.target_arg("^/trunk"), "^/trunk@HEAD");
+        assert_eq!(ListOptions::default().target_arg("^/trunk"), "^/trunk");
+    }
+
+    const LIST_XML_EXTERNALS: &str = r##"
+<?xml version="1.0" encoding="UTF-8"?>
+<lists>
+<list
+   path="https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source">
+<entry
+   kind="dir">
+<name>lib</name>
+<commit
+   revision="317527">
+<author>rajput</author>
+<date>2021-05-10T06:01:50.939035Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>manifest</name>
+<size>15987</size>
+<commit
+   revision="298675">
+<author>rajput</author>
+<date>2020-09-16T06:28:02.597638Z</date>
+</commit>
+<lock>
+<token>opaquelocktoken:2bc9a8a6-3f0d-4c4a-9d0b-f0e4c8b2a111</token>
+<owner>goela</owner>
+<comment>updating paytable</comment>
+<created>2021-08-21T09:12:43.107212Z</created>
+</lock>
+</entry>
+<external
+   parent_url="https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/lib"
+   target="NitroCommon">
+<entry
+   kind="file">
+<name>Common.cpp</name>
+<size>4512</size>
+<commit
+   revision="310568">
+<author>rajput</author>
+<date>2021-02-04T08:51:11.394823Z</date>
+</commit>
+</entry>
+<entry
+   kind="file">
+<name>Common.h</name>
+<size>808</size>
+<commit
+   revision="310568">
+<author>rajput</author>
+<date>2021-02-04T08:51:11.394823Z</date>
+</commit>
+</entry>
+</external>
+<external
+   parent-url="https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/lib"
+   target="NitroParticles">
+<entry
+   kind="file">
+<name>Particles.cpp</name>
+<size>9120</size>
+<commit
+   revision="313388">
+<author>rajput</author>
+<date>2021-03-09T07:31:23.286485Z</date>
+</commit>
+</entry>
+</external>
+</list>
+<list
+   path="https://svn.ali.global/GDK_games/GDK_games/BLS/NYL/trunk/source/src">
+<entry
+   kind="file">
+<name>Game.cpp</name>
+<size>2437</size>
+<commit
+   revision="324641">
+<author>rs102580</author>
+<date>2021-08-20T10:46:56.484066Z</date>
+</commit>
+</entry>
+</list>
+</lists>
+    "##;
+
     const LIST_XML_1: &str = r##"
 <?xml version="1.0" encoding="UTF-8"?>
 <lists>
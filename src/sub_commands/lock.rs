@@ -0,0 +1,224 @@
+use crate::{
+    cmd_wrapper::{stderr_error, CmdOutput},
+    SvnError,
+};
+use regex::Regex;
+use std::{collections::HashSet, sync::LazyLock};
+
+static LOCKED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^'(.*)' locked by user '(.*)'\.$").unwrap());
+static UNLOCKED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^'(.*)' unlocked\.$").unwrap());
+static WARNING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^svn: warning: (W\d+): (.*)$").unwrap());
+static ERROR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^svn: (E\d+): (.*)$").unwrap());
+static QUOTED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"'([^']*)'").unwrap());
+static OWNER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"by user '([^']*)'").unwrap());
+
+/// outcome of locking or unlocking one path
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockResult {
+    /// path as reported by svn, repository path for the failures
+    pub path: String,
+    /// lock taken or released
+    pub success: bool,
+    /// owner of the new lock, or of the lock in the way, e.g. for W160035
+    pub owner: Option<String>,
+    /// warning or error code of a failure, e.g. W160035 for a path already locked by someone
+    /// else
+    pub code: Option<String>,
+    /// line printed by svn
+    pub message: String,
+}
+
+/// results of `svn lock` or `svn unlock`, an error stopping svn half way comes last as a
+/// failure, the error when svn failed without reporting a path
+pub(crate) fn parse_lock_output(out: &CmdOutput) -> Result<Vec<LockResult>, SvnError> {
+    let mut results = Vec::new();
+    for line in out.stdout.lines().map(str::trim) {
+        if let Some(caps) = LOCKED.captures(line) {
+            results.push(LockResult {
+                path: caps[1].to_owned(),
+                success: true,
+                owner: Some(caps[2].to_owned()),
+                code: None,
+                message: line.to_owned(),
+            });
+        } else if let Some(caps) = UNLOCKED.captures(line) {
+            results.push(LockResult {
+                path: caps[1].to_owned(),
+                success: true,
+                owner: None,
+                code: None,
+                message: line.to_owned(),
+            });
+        }
+    }
+    let failure = |code: &str, message: &str| LockResult {
+        path: QUOTED
+            .captures(message)
+            .map(|c| c[1].to_owned())
+            .unwrap_or_default(),
+        success: false,
+        owner: OWNER.captures(message).map(|c| c[1].to_owned()),
+        code: Some(code.to_owned()),
+        message: message.to_owned(),
+    };
+    let mut errors: Vec<LockResult> = Vec::new();
+    for line in out.stderr.lines().map(str::trim) {
+        if let Some(caps) = WARNING.captures(line) {
+            results.push(failure(&caps[1], &caps[2]));
+        } else if let Some(caps) = ERROR.captures(line) {
+            // one entry per error, its message may take several lines
+            match errors.last_mut() {
+                Some(last) if last.code.as_deref() == Some(&caps[1]) => {
+                    last.message = format!("{}\n{}", last.message, &caps[2]);
+                }
+                _ => errors.push(failure(&caps[1], &caps[2])),
+            }
+        }
+    }
+    if !out.success && results.is_empty() {
+        return Err(stderr_error(out.stderr.clone()));
+    }
+    // svn ends with "E200009: One or more locks could not be obtained" after the warnings,
+    // other errors stopped svn, the paths done before it are still reported
+    if results.iter().any(|r| !r.success) {
+        errors.retain(|e| e.code.as_deref() != Some(NOT_ALL_LOCKED));
+    }
+    results.extend(errors);
+    Ok(results)
+}
+
+/// paths relative to `target` of the `svn:needs-lock` urls not in `locked`, propget prints
+/// uri encoded urls and list the decoded names
+pub(crate) fn unlocked_paths<'a>(
+    target: &str,
+    needs_lock: impl Iterator<Item = &'a str>,
+    locked: &HashSet<String>,
+) -> Vec<String> {
+    let prefix = format!("{}/", uri_decode(target.trim_end_matches('/')));
+    needs_lock
+        .filter_map(|url| uri_decode(url).strip_prefix(&prefix).map(str::to_owned))
+        .filter(|path| !locked.contains(path))
+        .collect()
+}
+
+/// `%XX` escapes of an url decoded, e.g. `Shared%20Fonts` to `Shared Fonts`
+pub(crate) fn uri_decode(url: &str) -> String {
+    let bytes = url.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// summary error after the per path warnings of svn lock and svn unlock
+const NOT_ALL_LOCKED: &str = "E200009";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_output() {
+        let out = CmdOutput {
+            success: false,
+            stdout: "'Peacock_bg.png' locked by user 'rs102580'.\n".to_owned(),
+            stderr: LOCK_STDERR.to_owned(),
+        };
+        let results = parse_lock_output(&out).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert_eq!(results[0].path, "Peacock_bg.png");
+        assert_eq!(results[0].owner.as_deref(), Some("rs102580"));
+        assert!(!results[1].success);
+        assert_eq!(results[1].path, "/GDK_games/Peacock/trunk/art/reels.psd");
+        assert_eq!(results[1].owner.as_deref(), Some("sn100722"));
+        assert_eq!(results[1].code.as_deref(), Some("W160035"));
+
+        let out = CmdOutput {
+            success: true,
+            stdout: "'Peacock_bg.png' unlocked.\n".to_owned(),
+            stderr: String::new(),
+        };
+        let results = parse_lock_output(&out).unwrap();
+        assert_eq!(results[0].path, "Peacock_bg.png");
+        assert!(results[0].success && results[0].owner.is_none());
+
+        let out = CmdOutput {
+            success: false,
+            stdout: String::new(),
+            stderr: "svn: E155010: The node 'missing.png' was not found.\n".to_owned(),
+        };
+        assert!(parse_lock_output(&out).is_err());
+        let out = CmdOutput {
+            success: false,
+            stdout: String::new(),
+            stderr: "svn: E155036: Please see the 'svn upgrade' command\n".to_owned(),
+        };
+        assert!(matches!(
+            parse_lock_output(&out),
+            Err(SvnError::WcNeedsUpgrade(_))
+        ));
+
+        // svn stopped on an error after locking the first path
+        let out = CmdOutput {
+            success: false,
+            stdout: "'Peacock_bg.png' locked by user 'rs102580'.\n".to_owned(),
+            stderr: "svn: E155010: The node 'C:\\wc\\missing.png' was not found.\n".to_owned(),
+        };
+        let results = parse_lock_output(&out).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert_eq!(results[1].code.as_deref(), Some("E155010"));
+        assert_eq!(results[1].path, "C:\\wc\\missing.png");
+    }
+
+    #[test]
+    fn decode_urls() {
+        assert_eq!(
+            uri_decode("https://svn.ali.global/GDK_games/art/Shared%20Fonts/Gr%C3%BC%C3%9Fe.psd"),
+            "https://svn.ali.global/GDK_games/art/Shared Fonts/Grüße.psd"
+        );
+        assert_eq!(uri_decode("100%25 %zz %2"), "100% %zz %2");
+    }
+
+    #[test]
+    fn unlocked_needs_lock() {
+        let target = "https://svn.ali.global/GDK_games/Peacock/trunk/art";
+        let needs_lock = [
+            "https://svn.ali.global/GDK_games/Peacock/trunk/art/reels.psd",
+            "https://svn.ali.global/GDK_games/Peacock/trunk/art/Shared%20Fonts/title.psd",
+            "https://svn.ali.global/GDK_games/Peacock/trunk/art/Gr%C3%BC%C3%9Fe.psd",
+            "https://svn.ali.global/GDK_games/Peacock/trunk/art/bg%20final.psd",
+        ];
+        let locked: HashSet<String> = ["Shared Fonts/title.psd", "Grüße.psd"]
+            .into_iter()
+            .map(str::to_owned)
+            .collect();
+        assert_eq!(
+            unlocked_paths(target, needs_lock.into_iter(), &locked),
+            vec!["reels.psd", "bg final.psd"]
+        );
+    }
+
+    const LOCK_STDERR: &str = "svn: warning: W160035: Path '/GDK_games/Peacock/trunk/art/reels.psd' is already locked by user 'sn100722' in filesystem '/var/svn/GDK_games/db'
+svn: E200009: One or more locks could not be obtained
+";
+}
@@ -62,11 +62,17 @@ pub struct LogParser {
     logentry: Vec<LogEntry>,
 }
 
+/// one revision of svn log
 #[derive(Deserialize, Debug, Clone)]
 pub struct LogEntry {
+    /// revision number
+    #[serde(rename(deserialize = "@revision"))]
     pub revision: u32,
+    /// author name
     pub author: String,
+    /// date of commit
     pub date: String,
+    /// log message
     pub msg: String,
 }
 
@@ -83,7 +89,7 @@ impl Iterator for SvnLog {
 
 impl LogParser {
     fn parse(text: &str) -> Result<Self, SvnError> {
-        serde_xml_rs::from_str::<Self>(text.trim()).map_err(SvnError::Deserializer)
+        quick_xml::de::from_str::<Self>(text.trim()).map_err(SvnError::Deserializer)
     }
 }
 
@@ -102,7 +108,7 @@ mod tests {
 
     #[test]
     fn parse() {
-        let de = serde_xml_rs::from_str::<LogParser>(LOG_SAMPLE).unwrap();
+        let de = quick_xml::de::from_str::<LogParser>(LOG_SAMPLE.trim()).unwrap();
         println!("{:?}", de);
     }
 
@@ -0,0 +1,100 @@
+use crate::{
+    merge_info::RevisionRange,
+    types::{Depth, RevisionType, ToCmdArgList},
+    SvnError,
+};
+
+/// revisions reported by `svn mergeinfo --show-revs ARG`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShowRevs {
+    /// revisions of the source not merged into the target yet
+    #[default]
+    Eligible,
+    /// revisions of the source already merged into the target
+    Merged,
+}
+
+/// options of svn-mergeinfo command
+#[derive(Debug, Clone, Default)]
+pub struct MergeinfoOptions {
+    /// `--show-revs ARG`
+    pub show_revs: ShowRevs,
+    /// `--recursive`, also report the merges into the children of the target
+    pub recursive: bool,
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--revision START:END`, only the source revisions of this range
+    pub revision: Option<(RevisionType, RevisionType)>,
+}
+
+impl ToCmdArgList for MergeinfoOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let show_revs = match self.show_revs {
+            ShowRevs::Eligible => "eligible",
+            ShowRevs::Merged => "merged",
+        };
+        let mut args = vec!["--show-revs".to_owned(), show_revs.to_owned()];
+        if self.recursive {
+            args.push("--recursive".to_owned());
+        }
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        if let Some((start, end)) = self.revision {
+            args.push("--revision".to_owned());
+            args.push(format!("{start}:{end}"));
+        }
+        args
+    }
+}
+
+/// parse the `rN` lines of `svn mergeinfo --show-revs`, a trailing `*` marks a revision merged
+/// into part of the target only
+pub(crate) fn parse_revisions(out: &str) -> Result<Vec<RevisionRange>, SvnError> {
+    let mut ranges: Vec<RevisionRange> = Vec::new();
+    for line in out.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let (revision, inheritable) = match line.strip_suffix('*') {
+            Some(revision) => (revision, false),
+            None => (line, true),
+        };
+        let revision: u64 = revision
+            .strip_prefix('r')
+            .and_then(|r| r.parse().ok())
+            .ok_or_else(|| SvnError::Other(format!("unexpected svn mergeinfo line '{line}'")))?;
+        match ranges.last_mut() {
+            Some(last) if last.end + 1 == revision && last.inheritable == inheritable => {
+                last.end = revision
+            }
+            _ => ranges.push(RevisionRange {
+                start: revision,
+                end: revision,
+                inheritable,
+            }),
+        }
+    }
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn show_revs_output() {
+        let ranges = parse_revisions(ELIGIBLE).unwrap();
+        assert_eq!(
+            ranges.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            vec!["310002-310004", "310007*", "310010"]
+        );
+        assert!(parse_revisions("").unwrap().is_empty());
+        assert!(parse_revisions("svn: E195012: Unable to find a common ancestor").is_err());
+    }
+
+    const ELIGIBLE: &str = "r310002
+r310003
+r310004
+r310007*
+r310010
+";
+}
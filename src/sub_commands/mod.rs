@@ -1,7 +1,16 @@
+pub(crate) mod blame;
+pub(crate) mod cleanup;
+pub(crate) mod commit;
+pub(crate) mod diff;
 pub(crate) mod info;
 pub(crate) mod list;
+pub(crate) mod lock;
 pub(crate) mod log;
+pub(crate) mod mergeinfo;
 pub(crate) mod prop_get;
 pub(crate) mod prop_list;
+pub(crate) mod prop_set;
+pub(crate) mod resolve;
+pub(crate) mod revert;
 pub(crate) mod status;
 pub(crate) mod version;
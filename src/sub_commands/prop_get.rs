@@ -1,60 +1,333 @@
-use crate::SvnError;
+use crate::{
+    externals::{ExternalDefinition, SvnExternals},
+    types::{Depth, RevisionType, ToCmdArgList},
+    SvnError,
+};
 use itertools::Itertools;
 use log::error;
+use quick_xml::{events::Event, Reader};
 use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+/// options of svn-propget command
+#[derive(Debug, Clone, Default)]
+pub struct PropgetOptions {
+    /// `--recursive`, same as `--depth infinity`
+    pub recursive: bool,
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--revprop`, read a revision property, needs `revision`
+    pub revprop: bool,
+    /// `--revision ARG`
+    pub revision: Option<RevisionType>,
+    /// `--show-inherited-props`, also report the values set on the parents, needs svn 1.8+
+    pub show_inherited_props: bool,
+}
+
+impl ToCmdArgList for PropgetOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.recursive {
+            args.push("--recursive".to_owned());
+        }
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        if self.revprop {
+            args.push("--revprop".to_owned());
+        }
+        if let Some(revision) = self.revision {
+            args.push("--revision".to_owned());
+            args.push(revision.to_string());
+        }
+        if self.show_inherited_props {
+            args.push("--show-inherited-props".to_owned());
+        }
+        args
+    }
+}
+
+/// svn propget
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
 pub struct SvnPropget {
-    /// target
-    pub target: Target,
+    /// one target per path having the property, inherited values come in the targets of
+    /// the parents they are set on
+    #[serde(rename(deserialize = "target"), default)]
+    pub targets: Vec<Target>,
+    /// revision property, only with `--revprop`
+    pub revprops: Option<RevProps>,
 }
 
+/// `<target>` block, one path and its values
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Target {
     /// requested url
+    #[serde(rename(deserialize = "@path"))]
     pub path: String,
     /// property entry
+    #[serde(default)]
     pub property: Vec<Property>,
+    /// values inherited by the requested target, `path` is where they are set
+    #[serde(rename(deserialize = "inherited_property"), default)]
+    pub inherited: Vec<Property>,
 }
 
+/// `<revprops>` block of `svn propget --revprop`
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RevProps {
+    /// revision the properties belong to
+    #[serde(rename(deserialize = "@rev"))]
+    pub revision: u64,
+    /// property entry
+    #[serde(default)]
+    pub property: Vec<Property>,
+}
+
+/// property name and value
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Property {
     /// name of the property
-    #[serde(deserialize_with = "deserialize_property_name")]
+    #[serde(
+        rename(deserialize = "@name"),
+        deserialize_with = "deserialize_property_name"
+    )]
     pub name: PropertyName,
-    #[serde(deserialize_with = "deserialize_external_paths")]
-    #[serde(rename = "$value")]
-    pub paths: Vec<ExternalPath>,
+    /// value as svn printed it, surrounding whitespace and trailing newline included
+    #[serde(rename(deserialize = "$text"), default)]
+    pub value: String,
 }
 
 impl SvnPropget {
     /// parse XML text
     pub(crate) fn parse(xml_text: &str) -> Result<Self, SvnError> {
-        serde_xml_rs::from_str::<Self>(xml_text.trim()).map_err(|e| {
-            error!("serde_xml_rs parsing error '{e:?}'");
+        let mut props = quick_xml::de::from_str::<Self>(xml_text.trim()).map_err(|e| {
+            error!("quick_xml parsing error '{e:?}'");
             SvnError::Deserializer(e)
-        })
+        })?;
+        keep_raw_values(xml_text, &mut props.targets, props.revprops.as_mut())?;
+        Ok(props)
+    }
+
+    /// first target having the property itself, the requested one when a single target
+    /// was passed
+    pub fn target(&self) -> Option<&Target> {
+        self.targets.iter().find(|t| !t.property.is_empty())
+    }
+
+    /// value of the first target, or of the revision property with `--revprop`
+    pub fn value(&self) -> Option<&str> {
+        self.values()
+            .map(|(_, p)| p.value.as_str())
+            .next()
+            .or_else(|| {
+                self.revprops
+                    .as_ref()
+                    .and_then(|r| r.property.first())
+                    .map(|p| p.value.as_str())
+            })
+    }
+
+    /// path and property of every target having the property itself
+    pub fn values(&self) -> impl Iterator<Item = (&str, &Property)> {
+        self.targets
+            .iter()
+            .flat_map(|t| t.property.iter().map(move |p| (t.path.as_str(), p)))
+    }
+
+    /// path the value is set on and the inherited property, nearest parent last
+    pub fn inherited(&self) -> impl Iterator<Item = (&str, &Property)> {
+        self.targets
+            .iter()
+            .flat_map(|t| t.inherited.iter().map(move |p| (t.path.as_str(), p)))
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+impl Property {
+    /// value split into its non empty lines, e.g. the patterns of `svn:ignore`
+    pub fn lines(&self) -> Vec<&str> {
+        self.value
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect()
+    }
+
+    /// value read as `svn:externals` definitions
+    pub fn externals(&self) -> Result<SvnExternals, SvnError> {
+        SvnExternals::parse(&self.value)
+    }
+
+    /// url and local name of every valid `svn:externals` definition, invalid lines are
+    /// skipped
+    pub fn external_paths(&self) -> Vec<ExternalPath> {
+        self.value
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| ExternalDefinition::parse(line).ok())
+            .map(|def| ExternalPath {
+                name: def.target,
+                relative_path: def.url,
+            })
+            .collect_vec()
+    }
+}
+
+/// put back the whitespace the deserializer trims off the property values, the values are
+/// read again untrimmed, in document order
+pub(crate) fn keep_raw_values(
+    xml_text: &str,
+    targets: &mut [Target],
+    revprops: Option<&mut RevProps>,
+) -> Result<(), SvnError> {
+    let mut values = Vec::new();
+    let mut inherited = Vec::new();
+    let mut current: Option<(bool, String)> = None;
+    let mut reader = Reader::from_str(xml_text);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if is_property(e.name().as_ref()) => {
+                current = Some((e.name().as_ref() == b"inherited_property", String::new()));
+            }
+            Event::Empty(e) if is_property(e.name().as_ref()) => {
+                if e.name().as_ref() == b"inherited_property" {
+                    inherited.push(String::new());
+                } else {
+                    values.push(String::new());
+                }
+            }
+            Event::Text(e) => {
+                if let Some((_, value)) = current.as_mut() {
+                    value.push_str(&e.unescape()?);
+                }
+            }
+            Event::CData(e) => {
+                if let Some((_, value)) = current.as_mut() {
+                    value.push_str(&String::from_utf8_lossy(&e));
+                }
+            }
+            Event::End(e) if is_property(e.name().as_ref()) => match current.take() {
+                Some((true, value)) => inherited.push(value),
+                Some((false, value)) => values.push(value),
+                None => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let mut values = values.into_iter();
+    let mut inherited = inherited.into_iter();
+    for target in targets {
+        for (property, value) in target.property.iter_mut().zip(values.by_ref()) {
+            property.value = value;
+        }
+        for (property, value) in target.inherited.iter_mut().zip(inherited.by_ref()) {
+            property.value = value;
+        }
+    }
+    for (property, value) in revprops
+        .into_iter()
+        .flat_map(|r| r.property.iter_mut())
+        .zip(values)
+    {
+        property.value = value;
+    }
+    Ok(())
+}
+
+fn is_property(name: &[u8]) -> bool {
+    name == b"property" || name == b"inherited_property"
+}
+
+/// property name, the well known `svn:` ones are typed
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum PropertyName {
+    /// `svn:externals`
     SvnExternals,
+    /// `svn:ignore`
+    SvnIgnore,
+    /// `svn:global-ignores`
+    SvnGlobalIgnores,
+    /// `svn:keywords`
+    SvnKeywords,
+    /// `svn:eol-style`
+    SvnEolStyle,
+    /// `svn:executable`
+    SvnExecutable,
+    /// `svn:needs-lock`
+    SvnNeedsLock,
+    /// `svn:mime-type`
+    SvnMimeType,
+    /// `svn:mergeinfo`
+    SvnMergeinfo,
+    /// `svn:auto-props`
+    SvnAutoProps,
+    /// `svn:log` revision property, the commit message
+    SvnLog,
+    /// `svn:author` revision property
+    SvnAuthor,
+    /// `svn:date` revision property
+    SvnDate,
+    /// any other property
     Other(String),
 }
 
 impl PropertyName {
-    pub(crate) fn as_str(&self) -> &str {
+    /// name as svn knows it
+    pub fn as_str(&self) -> &str {
         match self {
             Self::SvnExternals => "svn:externals",
+            Self::SvnIgnore => "svn:ignore",
+            Self::SvnGlobalIgnores => "svn:global-ignores",
+            Self::SvnKeywords => "svn:keywords",
+            Self::SvnEolStyle => "svn:eol-style",
+            Self::SvnExecutable => "svn:executable",
+            Self::SvnNeedsLock => "svn:needs-lock",
+            Self::SvnMimeType => "svn:mime-type",
+            Self::SvnMergeinfo => "svn:mergeinfo",
+            Self::SvnAutoProps => "svn:auto-props",
+            Self::SvnLog => "svn:log",
+            Self::SvnAuthor => "svn:author",
+            Self::SvnDate => "svn:date",
             Self::Other(other) => other,
         }
     }
 }
 
+impl From<&str> for PropertyName {
+    fn from(name: &str) -> Self {
+        match name {
+            "svn:externals" => Self::SvnExternals,
+            "svn:ignore" => Self::SvnIgnore,
+            "svn:global-ignores" => Self::SvnGlobalIgnores,
+            "svn:keywords" => Self::SvnKeywords,
+            "svn:eol-style" => Self::SvnEolStyle,
+            "svn:executable" => Self::SvnExecutable,
+            "svn:needs-lock" => Self::SvnNeedsLock,
+            "svn:mime-type" => Self::SvnMimeType,
+            "svn:mergeinfo" => Self::SvnMergeinfo,
+            "svn:auto-props" => Self::SvnAutoProps,
+            "svn:log" => Self::SvnLog,
+            "svn:author" => Self::SvnAuthor,
+            "svn:date" => Self::SvnDate,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+}
+
+impl Display for PropertyName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// one `svn:externals` definition
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ExternalPath {
+    /// local name of the external
     pub name: String,
+    /// url of the external as written in the definition
     pub relative_path: String,
 }
 
@@ -63,24 +336,7 @@ where
     D: Deserializer<'de>,
 {
     let s: String = Deserialize::deserialize(deserializer)?;
-    Ok(match s.as_str() {
-        "svn:externals" => PropertyName::SvnExternals,
-        other => PropertyName::Other(other.to_owned()),
-    })
-}
-
-fn deserialize_external_paths<'de, D>(deserializer: D) -> Result<Vec<ExternalPath>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s: String = Deserialize::deserialize(deserializer)?;
-    Ok(s.split_whitespace()
-        .tuples()
-        .map(|(relative_path, name)| ExternalPath {
-            name: name.to_owned(),
-            relative_path: relative_path.to_owned(),
-        })
-        .collect_vec())
+    Ok(PropertyName::from(s.as_str()))
 }
 
 #[cfg(test)]
@@ -92,9 +348,101 @@ mod tests {
     fn prop_get() -> AnyResult<()> {
         let props = SvnPropget::parse(PROP_LIST)?;
         println!("{props:#?}");
+        let property = &props.target().unwrap().property[0];
+        assert_eq!(property.name, PropertyName::SvnExternals);
+        let paths = property.external_paths();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[1].name, "NitroCommon");
+
+        // an invalid line only drops itself
+        let property = Property {
+            name: PropertyName::SvnExternals,
+            value: "^/NitroCommon/trunk NitroCommon\nbroken\n^/Particles/trunk Particles\n"
+                .to_owned(),
+        };
+        let names: Vec<_> = property
+            .external_paths()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["NitroCommon", "Particles"]);
+        assert!(property.externals().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn raw_values_recursive() -> AnyResult<()> {
+        let props = SvnPropget::parse(PROP_IGNORE_RECURSIVE)?;
+        let values: Vec<_> = props.values().map(|(path, p)| (path, p.lines())).collect();
+        assert_eq!(
+            values,
+            vec![
+                ("source", vec!["*.obj", "Debug", "Release"]),
+                ("source/assets", vec!["*.tmp"]),
+            ]
+        );
+        assert_eq!(
+            props.values().next().unwrap().1.name,
+            PropertyName::SvnIgnore
+        );
         Ok(())
     }
 
+    #[test]
+    fn untrimmed_values() -> AnyResult<()> {
+        let props = SvnPropget::parse(PROP_WHITESPACE)?;
+        let values: Vec<_> = props.values().map(|(_, p)| p.value.as_str()).collect();
+        assert_eq!(values, vec!["*.obj\nDebug\n", "  indented &\n\n", ""]);
+        let props = SvnPropget::parse(PROP_IGNORE_RECURSIVE)?;
+        assert_eq!(props.value(), Some("*.obj\nDebug\nRelease\n"));
+        let props = SvnPropget::parse(PROP_REVPROP_MULTILINE)?;
+        assert_eq!(props.value(), Some("  Creating RC03 tag\n\n  from RC02\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn inherited_values() -> AnyResult<()> {
+        let props = SvnPropget::parse(PROP_INHERITED)?;
+        let inherited: Vec<_> = props
+            .inherited()
+            .map(|(path, p)| (path, p.value.trim()))
+            .collect();
+        assert_eq!(
+            inherited,
+            vec![
+                ("https://svn.ali.global/GDK_games", "*.bak"),
+                ("https://svn.ali.global/GDK_games/GDK_games/BLS", "*.log"),
+            ]
+        );
+        assert_eq!(props.target().unwrap().path, "source");
+        assert_eq!(props.value().map(str::trim), Some("*.obj"));
+        Ok(())
+    }
+
+    #[test]
+    fn revprop() -> AnyResult<()> {
+        let props = SvnPropget::parse(PROP_REVPROP)?;
+        assert!(props.targets.is_empty());
+        assert_eq!(props.revprops.as_ref().unwrap().revision, 324641);
+        assert_eq!(props.value(), Some("Creating RC03 tag"));
+        Ok(())
+    }
+
+    #[test]
+    fn property_names() {
+        for name in ["svn:externals", "svn:eol-style", "svn:log", "bugtraq:url"] {
+            assert_eq!(PropertyName::from(name).as_str(), name);
+        }
+        assert_eq!(
+            PropertyName::from("svn:needs-lock"),
+            PropertyName::SvnNeedsLock
+        );
+        assert_eq!(
+            PropertyName::from("svn:foo"),
+            PropertyName::Other("svn:foo".to_owned())
+        );
+    }
+
     const PROP_LIST: &str = r##"
     <?xml version="1.0" encoding="UTF-8"?>
     <properties>
@@ -106,4 +454,87 @@ mod tests {
     </target>
     </properties>
     "##;
+
+    const PROP_IGNORE_RECURSIVE: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="source">
+<property
+   name="svn:ignore">*.obj
+Debug
+Release
+</property>
+</target>
+<target
+   path="source/assets">
+<property
+   name="svn:ignore">*.tmp
+</property>
+</target>
+</properties>
+"##;
+
+    const PROP_INHERITED: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="https://svn.ali.global/GDK_games">
+<inherited_property
+   name="svn:global-ignores">*.bak
+</inherited_property>
+</target>
+<target
+   path="https://svn.ali.global/GDK_games/GDK_games/BLS">
+<inherited_property
+   name="svn:global-ignores">*.log
+</inherited_property>
+</target>
+<target
+   path="source">
+<property
+   name="svn:global-ignores">*.obj
+</property>
+</target>
+</properties>
+"##;
+
+    const PROP_WHITESPACE: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="source">
+<property
+   name="svn:ignore">*.obj
+Debug
+</property>
+<property
+   name="bugtraq:message">  indented &amp;
+
+</property>
+<property
+   name="svn:needs-lock"/>
+</target>
+</properties>
+"##;
+
+    const PROP_REVPROP_MULTILINE: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<revprops
+   rev="324641">
+<property
+   name="svn:log">  Creating RC03 tag
+
+  from RC02
+</property>
+</revprops>
+</properties>
+"##;
+
+    const PROP_REVPROP: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<revprops
+   rev="324641">
+<property
+   name="svn:log">Creating RC03 tag</property>
+</revprops>
+</properties>
+"##;
 }
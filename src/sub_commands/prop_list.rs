@@ -1,36 +1,116 @@
-use super::prop_get::{deserialize_property_name, PropertyName};
-use crate::SvnError;
+use super::prop_get::{keep_raw_values, Property, PropertyName, RevProps, Target};
+use crate::{
+    types::{Depth, RevisionType, ToCmdArgList},
+    SvnError,
+};
 use log::error;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
-pub struct SvnProplist {
-    /// target
-    pub target: Option<Target>,
+/// options of svn-proplist command
+#[derive(Debug, Clone, Default)]
+pub struct ProplistOptions {
+    /// `--verbose`, report the values too, they are empty otherwise
+    pub verbose: bool,
+    /// `--recursive`, same as `--depth infinity`
+    pub recursive: bool,
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--revprop`, list the revision properties, needs `revision`
+    pub revprop: bool,
+    /// `--revision ARG`
+    pub revision: Option<RevisionType>,
+    /// `--show-inherited-props`, also report the properties set on the parents, needs svn 1.8+
+    pub show_inherited_props: bool,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
-pub struct Target {
-    /// requested url
-    pub path: String,
-    /// property entry
-    pub property: Vec<Property>,
+impl ToCmdArgList for ProplistOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.verbose {
+            args.push("--verbose".to_owned());
+        }
+        if self.recursive {
+            args.push("--recursive".to_owned());
+        }
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        if self.revprop {
+            args.push("--revprop".to_owned());
+        }
+        if let Some(revision) = self.revision {
+            args.push("--revision".to_owned());
+            args.push(revision.to_string());
+        }
+        if self.show_inherited_props {
+            args.push("--show-inherited-props".to_owned());
+        }
+        args
+    }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
-pub struct Property {
-    /// name of the property
-    #[serde(deserialize_with = "deserialize_property_name")]
-    pub name: PropertyName,
+/// svn proplist
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct SvnProplist {
+    /// one target per path having properties, inherited properties come in the targets of
+    /// the parents they are set on
+    #[serde(rename(deserialize = "target"), default)]
+    pub targets: Vec<Target>,
+    /// revision properties, only with `--revprop`
+    pub revprops: Option<RevProps>,
 }
 
+/// property values of one path
+pub type PropertyMap = BTreeMap<PropertyName, String>;
+
 impl SvnProplist {
     /// parse XML text
     pub(crate) fn parse(xml_text: &str) -> Result<Self, SvnError> {
-        serde_xml_rs::from_str::<Self>(xml_text.trim()).map_err(|e| {
-            error!("serde_xml_rs parsing error '{e:?}'");
+        let mut props = quick_xml::de::from_str::<Self>(xml_text.trim()).map_err(|e| {
+            error!("quick_xml parsing error '{e:?}'");
             SvnError::Deserializer(e)
-        })
+        })?;
+        keep_raw_values(xml_text, &mut props.targets, props.revprops.as_mut())?;
+        Ok(props)
+    }
+
+    /// first target having properties itself, the requested one when a single target
+    /// was passed
+    pub fn target(&self) -> Option<&Target> {
+        self.targets.iter().find(|t| !t.property.is_empty())
+    }
+
+    /// properties set on every listed path
+    pub fn by_path(&self) -> BTreeMap<String, PropertyMap> {
+        Self::collect(self.targets.iter().map(|t| (&t.path, &t.property)))
+    }
+
+    /// properties inherited by the requested targets, by the path they are set on
+    pub fn inherited_by_path(&self) -> BTreeMap<String, PropertyMap> {
+        Self::collect(self.targets.iter().map(|t| (&t.path, &t.inherited)))
+    }
+
+    /// revision properties, empty without `--revprop`
+    pub fn revision_props(&self) -> PropertyMap {
+        self.revprops
+            .iter()
+            .flat_map(|r| r.property.iter())
+            .map(|p| (p.name.clone(), p.value.clone()))
+            .collect()
+    }
+
+    fn collect<'a>(
+        targets: impl Iterator<Item = (&'a String, &'a Vec<Property>)>,
+    ) -> BTreeMap<String, PropertyMap> {
+        let mut map: BTreeMap<String, PropertyMap> = BTreeMap::new();
+        for (path, properties) in targets.filter(|(_, p)| !p.is_empty()) {
+            map.entry(path.clone())
+                .or_default()
+                .extend(properties.iter().map(|p| (p.name.clone(), p.value.clone())));
+        }
+        map
     }
 }
 
@@ -43,6 +123,46 @@ mod tests {
     fn prop_list() -> AnyResult<()> {
         let props = SvnProplist::parse(PROP_LIST)?;
         println!("{props:#?}");
+        let property = &props.target().unwrap().property[0];
+        assert_eq!(property.name, PropertyName::SvnExternals);
+        assert_eq!(property.value, "");
+        Ok(())
+    }
+
+    #[test]
+    fn verbose_recursive() -> AnyResult<()> {
+        let props = SvnProplist::parse(PROP_LIST_VERBOSE)?;
+        let by_path = props.by_path();
+        assert_eq!(by_path.len(), 2);
+        let source = &by_path["source"];
+        assert_eq!(source[&PropertyName::SvnIgnore], "*.obj\nDebug\n");
+        assert_eq!(
+            source[&PropertyName::Other("bugtraq:url".to_owned())],
+            "https://jira.ali.global/browse/%BUGID%"
+        );
+        let manifest = &by_path["source/manifest"];
+        assert_eq!(manifest[&PropertyName::SvnMimeType], "text/xml");
+        assert_eq!(manifest[&PropertyName::SvnNeedsLock], "*");
+        assert!(props.inherited_by_path().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn inherited_and_revprops() -> AnyResult<()> {
+        let props = SvnProplist::parse(PROP_LIST_INHERITED)?;
+        let inherited = props.inherited_by_path();
+        assert_eq!(
+            inherited["https://svn.ali.global/GDK_games"][&PropertyName::SvnGlobalIgnores],
+            "*.bak\n"
+        );
+        assert_eq!(props.target().unwrap().path, "source");
+
+        let props = SvnProplist::parse(PROP_LIST_REVPROPS)?;
+        assert!(props.targets.is_empty());
+        let revision_props = props.revision_props();
+        assert_eq!(revision_props[&PropertyName::SvnLog], "Creating RC03 tag");
+        assert_eq!(revision_props[&PropertyName::SvnAuthor], "rs102580");
+        assert_eq!(props.revprops.unwrap().revision, 324641);
         Ok(())
     }
 
@@ -55,6 +175,58 @@ mod tests {
         </properties>
     "##;
 
+    const PROP_LIST_VERBOSE: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="source">
+<property
+   name="svn:ignore">*.obj
+Debug
+</property>
+<property
+   name="bugtraq:url">https://jira.ali.global/browse/%BUGID%</property>
+</target>
+<target
+   path="source/manifest">
+<property
+   name="svn:mime-type">text/xml</property>
+<property
+   name="svn:needs-lock">*</property>
+</target>
+</properties>
+"##;
+
+    const PROP_LIST_INHERITED: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target
+   path="https://svn.ali.global/GDK_games">
+<inherited_property
+   name="svn:global-ignores">*.bak
+</inherited_property>
+</target>
+<target
+   path="source">
+<property
+   name="svn:ignore">*.obj
+</property>
+</target>
+</properties>
+"##;
+
+    const PROP_LIST_REVPROPS: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<revprops
+   rev="324641">
+<property
+   name="svn:author">rs102580</property>
+<property
+   name="svn:date">2021-08-20T10:46:56.484066Z</property>
+<property
+   name="svn:log">Creating RC03 tag</property>
+</revprops>
+</properties>
+"##;
+
     const _PROP_LIST_ERROR: &str = r##"
         <?xml version="1.0" encoding="UTF-8"?><properties>svn: E170013: Unable to connect to a repository at URL 'https://svn.ali.global/GDK_games/GDK_games/BLS/Class_II/FuDaiLianLian_Boost/MarsPortrait/Peacock/tags/gampro_usa_1.01_68099_RC05_PC01_Signed/source/liv'svn: E731001: No such host is known.
     "##;
@@ -0,0 +1,156 @@
+use crate::{
+    types::{Depth, RevisionType, ToCmdArgList},
+    SvnError,
+};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// options of svn-propset and svn-propdel commands
+#[derive(Debug, Clone, Default)]
+pub struct PropsetOptions {
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--changelist ARG`, only the paths in these changelists
+    pub changelists: Vec<String>,
+    /// `--force`, e.g. set `svn:eol-style` on a binary file, propset only
+    pub force: bool,
+    /// `--revprop`, change a revision property, needs `revision`
+    pub revprop: bool,
+    /// `--revision ARG`
+    pub revision: Option<RevisionType>,
+}
+
+impl ToCmdArgList for PropsetOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        for changelist in &self.changelists {
+            args.push("--changelist".to_owned());
+            args.push(changelist.clone());
+        }
+        if self.force {
+            args.push("--force".to_owned());
+        }
+        if self.revprop {
+            args.push("--revprop".to_owned());
+        }
+        if let Some(revision) = self.revision {
+            args.push("--revision".to_owned());
+            args.push(revision.to_string());
+        }
+        args
+    }
+}
+
+/// where `SvnCmd::prop_set_with_options` takes the value from
+pub enum PropValue<'a> {
+    /// value in memory
+    Text(&'a str),
+    /// file holding the value, `--file PATH`
+    File(&'a Path),
+    /// read to its end before svn runs, e.g. stdin
+    Reader(&'a mut dyn Read),
+}
+
+impl PropValue<'_> {
+    /// path for `--file` and the temporary file holding the value, kept until svn has run
+    pub(crate) fn file_arg(self) -> Result<(String, Option<ValueFile>), SvnError> {
+        let value_file = match self {
+            Self::Text(text) => ValueFile::new(text.as_bytes())?,
+            Self::File(path) => return Ok((path.to_string_lossy().into_owned(), None)),
+            Self::Reader(reader) => {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes)?;
+                ValueFile::new(&bytes)?
+            }
+        };
+        Ok((value_file.path_str(), Some(value_file)))
+    }
+}
+
+// value written to a temporary file for `--file`, removed on drop
+pub(crate) struct ValueFile(PathBuf);
+
+impl ValueFile {
+    // created with `create_new`, so a file or symlink planted at the name is never written
+    // through, another name is tried instead
+    pub(crate) fn new(value: &[u8]) -> Result<Self, SvnError> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let mut attempt = 0;
+        loop {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or_default();
+            let path = std::env::temp_dir().join(format!(
+                "svn-cmd-value-{}-{}-{nanos:08x}",
+                std::process::id(),
+                COUNT.fetch_add(1, Ordering::Relaxed)
+            ));
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    let value_file = Self(path);
+                    file.write_all(value)?;
+                    return Ok(value_file);
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempt < 16 => {
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    pub(crate) fn path_str(&self) -> String {
+        self.0.to_string_lossy().into_owned()
+    }
+}
+
+impl Drop for ValueFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_files() {
+        // written as is, a leading '-' and the trailing newline included
+        let (path, file) = PropValue::Text("-r 310568 ^/NitroCommon NitroCommon\n")
+            .file_arg()
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "-r 310568 ^/NitroCommon NitroCommon\n"
+        );
+        drop(file);
+        assert!(!Path::new(&path).exists());
+
+        let mut reader: &[u8] = b"*.obj\r\nDebug\r\n";
+        let (path, file) = PropValue::Reader(&mut reader).file_arg().unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"*.obj\r\nDebug\r\n");
+        let (other, _other_file) = PropValue::Text("").file_arg().unwrap();
+        assert_ne!(path, other);
+        assert_eq!(fs::read(&other).unwrap(), b"");
+        drop(file);
+
+        let (path, file) = PropValue::File(Path::new("value.txt")).file_arg().unwrap();
+        assert_eq!(path, "value.txt");
+        assert!(file.is_none());
+    }
+}
@@ -0,0 +1,57 @@
+/// how `svn resolve --accept ARG` resolves a conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accept {
+    /// the base file before the operation, the '.rOLD' file
+    Base,
+    /// the working file as it is now, conflict markers edited out by hand
+    Working,
+    /// local changes for the conflicting hunks only
+    MineConflict,
+    /// incoming changes for the conflicting hunks only
+    TheirsConflict,
+    /// the local file, '.mine', for the whole file
+    MineFull,
+    /// the incoming file, '.rNEW', for the whole file
+    TheirsFull,
+}
+
+impl Accept {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Base => "base",
+            Self::Working => "working",
+            Self::MineConflict => "mine-conflict",
+            Self::TheirsConflict => "theirs-conflict",
+            Self::MineFull => "mine-full",
+            Self::TheirsFull => "theirs-full",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_args() {
+        let all = [
+            Accept::Base,
+            Accept::Working,
+            Accept::MineConflict,
+            Accept::TheirsConflict,
+            Accept::MineFull,
+            Accept::TheirsFull,
+        ];
+        assert_eq!(
+            all.iter().map(Accept::as_str).collect::<Vec<_>>(),
+            vec![
+                "base",
+                "working",
+                "mine-conflict",
+                "theirs-conflict",
+                "mine-full",
+                "theirs-full"
+            ]
+        );
+    }
+}
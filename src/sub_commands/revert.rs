@@ -0,0 +1,30 @@
+use crate::types::{Depth, ToCmdArgList};
+
+/// options of svn-revert command
+#[derive(Debug, Clone, Default)]
+pub struct RevertOptions {
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--recursive`, same as `--depth infinity`
+    pub recursive: bool,
+    /// `--changelist ARG`, only revert the paths in these changelists
+    pub changelists: Vec<String>,
+}
+
+impl ToCmdArgList for RevertOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        if self.recursive {
+            args.push("--recursive".to_owned());
+        }
+        for changelist in &self.changelists {
+            args.push("--changelist".to_owned());
+            args.push(changelist.clone());
+        }
+        args
+    }
+}
@@ -1,67 +1,324 @@
 use crate::errors::SvnError;
+use crate::sub_commands::info::{EntryCommit, EntryLock};
+use crate::types::{Depth, ToCmdArgList};
 use log::trace;
 use serde::{
     de::{self, Deserializer},
     Deserialize,
 };
-use std::collections::{hash_map::Entry::Vacant, HashMap};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
+/// status of an item as reported by `svn status`
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 pub enum StatusItemType {
-    Unversioned,
-    External,
+    /// scheduled for addition
     Added,
-    Removed,
+    /// in a state of conflict
+    Conflicted,
+    /// scheduled for deletion
+    Deleted,
+    /// present because of an externals definition
+    External,
+    /// ignored by `svn:ignore` or the global ignores
+    Ignored,
+    /// directory doesn't contain a complete entries list
+    Incomplete,
+    /// missing from the working copy, deleted by a non-svn command
+    Missing,
+    /// content has been modified
     Modified,
+    /// no modifications
+    None,
+    /// no modifications, the item is versioned
+    Normal,
+    /// versioned as one kind but found as another, or obstructed by an unversioned item
+    Obstructed,
+    /// deleted and then re-added in its place
+    Replaced,
+    /// not under version control
+    Unversioned,
 }
 
 impl StatusItemType {
     fn parse<T: AsRef<str>>(item: T) -> Result<Self, SvnError> {
         match item.as_ref() {
-            "unversioned" => Ok(Self::Unversioned),
-            "modified" => Ok(Self::Modified),
             "added" => Ok(Self::Added),
+            "conflicted" => Ok(Self::Conflicted),
+            "deleted" => Ok(Self::Deleted),
             "external" => Ok(Self::External),
-            "removed" => Ok(Self::Removed),
-            _ => Err(SvnError::Other("unhandled item type".to_owned())),
+            "ignored" => Ok(Self::Ignored),
+            "incomplete" => Ok(Self::Incomplete),
+            "missing" => Ok(Self::Missing),
+            "modified" => Ok(Self::Modified),
+            "none" => Ok(Self::None),
+            "normal" => Ok(Self::Normal),
+            "obstructed" => Ok(Self::Obstructed),
+            "replaced" => Ok(Self::Replaced),
+            "unversioned" => Ok(Self::Unversioned),
+            other => Err(SvnError::Other(format!("unhandled item type '{other}'"))),
+        }
+    }
+}
+
+/// lock state of an entry, as shown in the sixth column of `svn status -u`
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum LockState {
+    /// not locked in the working copy nor in the repository
+    Unlocked,
+    /// locked in this working copy ('K')
+    Locked,
+    /// locked by some other user or working copy ('O')
+    OtherLocked,
+    /// locked in this working copy, but the lock has been stolen ('T')
+    Stolen,
+    /// locked in this working copy, but the lock has been broken ('B')
+    Broken,
+}
+
+/// options of svn-status command
+#[derive(Debug, Clone, Default)]
+pub struct StatusOptions {
+    /// `--depth ARG`
+    pub depth: Option<Depth>,
+    /// `--show-updates`, contact the repository and report incoming changes
+    pub show_updates: bool,
+    /// `--no-ignore`, report ignored items too
+    pub no_ignore: bool,
+    /// `--ignore-externals`
+    pub ignore_externals: bool,
+    /// `--quiet`, report only locally modified items
+    pub quiet: bool,
+    /// `--changelist ARG`, only report items in these changelists
+    pub changelists: Vec<String>,
+}
+
+impl ToCmdArgList for StatusOptions {
+    fn to_cmd_arg_list(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push("--depth".to_owned());
+            args.push(depth.as_str().to_owned());
+        }
+        if self.show_updates {
+            args.push("--show-updates".to_owned());
+        }
+        if self.no_ignore {
+            args.push("--no-ignore".to_owned());
         }
+        if self.ignore_externals {
+            args.push("--ignore-externals".to_owned());
+        }
+        if self.quiet {
+            args.push("--quiet".to_owned());
+        }
+        for changelist in &self.changelists {
+            args.push("--changelist".to_owned());
+            args.push(changelist.clone());
+        }
+        args
     }
 }
 
 /// represents output of svn-status command
 #[derive(Debug)]
 pub struct SvnStatus {
-    /// status data
+    /// one group per `<target>`, svn reports every requested target and each external
+    /// working copy as a separate target
+    pub targets: Vec<StatusTarget>,
+    /// entries belonging to changelists, grouped by changelist
+    pub changelists: Vec<StatusChangelist>,
+    /// status data, entry paths grouped by their item status
     pub status: HashMap<StatusItemType, Vec<PathBuf>>,
+    /// repository revision the first target was compared against, only with `--show-updates`
+    pub against_revision: Option<u32>,
+}
+
+/// entries reported under one `<target>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusTarget {
+    /// target path
+    pub path: PathBuf,
+    /// entries which aren't part of any changelist
+    pub entries: Vec<StatusEntry>,
+    /// repository revision the target was compared against, only with `--show-updates`
+    pub against_revision: Option<u32>,
+}
+
+/// entries reported under one `<changelist>`
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct StatusChangelist {
+    /// name of the changelist
+    #[serde(rename(deserialize = "@name"))]
+    pub name: String,
+    /// entries in the changelist
+    #[serde(rename(deserialize = "entry"), default)]
+    pub entries: Vec<StatusEntry>,
 }
 
 impl SvnStatus {
     pub(crate) fn parse<T: AsRef<str>>(text: T) -> Result<Self, SvnError> {
-        let status_vec = StatusParser::parse(text.as_ref().trim())?;
-        let mut status_map: HashMap<StatusItemType, Vec<PathBuf>> = HashMap::new();
-        for entry in status_vec.target.entry {
-            let item = entry.wc_status.item;
-            let path = entry.path;
-            if let Vacant(e) = status_map.entry(item) {
-                e.insert(vec![path]);
-            } else {
-                status_map.get_mut(&item).unwrap().push(path); // we have already placed a check so unwrap is fine here
-            }
+        let parsed = StatusParser::parse(text.as_ref().trim())?;
+        let targets: Vec<StatusTarget> = parsed
+            .target
+            .into_iter()
+            .map(|t| StatusTarget {
+                path: t.path,
+                entries: t.entry,
+                against_revision: t.against.map(|a| a.revision),
+            })
+            .collect();
+        let against_revision = targets.first().and_then(|t| t.against_revision);
+        let mut svn_status = SvnStatus {
+            targets,
+            changelists: parsed.changelist,
+            status: HashMap::new(),
+            against_revision,
+        };
+        let mut status: HashMap<StatusItemType, Vec<PathBuf>> = HashMap::new();
+        for entry in svn_status.entries() {
+            status
+                .entry(entry.wc_status.item)
+                .or_default()
+                .push(entry.path.clone());
+        }
+        svn_status.status = status;
+        Ok(svn_status)
+    }
+
+    /// every entry of every target and changelist, in the order svn printed them
+    pub fn entries(&self) -> impl Iterator<Item = &StatusEntry> {
+        self.targets
+            .iter()
+            .flat_map(|t| t.entries.iter())
+            .chain(self.changelists.iter().flat_map(|c| c.entries.iter()))
+    }
+
+    /// entries which have newer changes in the repository
+    pub fn out_of_date(&self) -> impl Iterator<Item = &StatusEntry> {
+        self.entries().filter(|e| e.is_out_of_date())
+    }
+
+    /// entries of the named changelist
+    pub fn changelist(&self, name: &str) -> Option<&StatusChangelist> {
+        self.changelists.iter().find(|c| c.name == name)
+    }
+
+    /// paths of every changelist, by changelist name
+    pub fn by_changelist(&self) -> BTreeMap<String, Vec<PathBuf>> {
+        let mut by_changelist: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for changelist in &self.changelists {
+            by_changelist
+                .entry(changelist.name.clone())
+                .or_default()
+                .extend(changelist.entries.iter().map(|e| e.path.clone()));
         }
-        Ok(SvnStatus { status: status_map })
+        by_changelist
     }
 }
 
+/// status of one path in the working copy
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct StatusEntry {
+    /// path of the entry, relative to the current dir or absolute as passed to svn
+    #[serde(rename(deserialize = "@path"), deserialize_with = "to_pathbuf")]
+    pub path: PathBuf,
+    /// working copy status
+    #[serde(rename(deserialize = "wc-status"))]
+    pub wc_status: WcStatus,
+    /// repository status, only present with `--show-updates`
+    #[serde(rename(deserialize = "repos-status"))]
+    pub repos_status: Option<ReposStatus>,
+}
+
+impl StatusEntry {
+    /// true if the repository has a newer text or props change for this entry
+    pub fn is_out_of_date(&self) -> bool {
+        self.repos_status
+            .as_ref()
+            .is_some_and(|r| r.item != StatusItemType::None || r.props != StatusItemType::None)
+    }
+
+    /// compares the working copy lock with the repository lock,
+    /// the result is meaningful only with `--show-updates`
+    pub fn lock_state(&self) -> LockState {
+        let wc_lock = self.wc_status.lock.as_ref();
+        let repos_lock = self.repos_status.as_ref().and_then(|r| r.lock.as_ref());
+        match (wc_lock, repos_lock) {
+            (None, None) => LockState::Unlocked,
+            (None, Some(_)) => LockState::OtherLocked,
+            (Some(_), None) if self.repos_status.is_some() => LockState::Broken,
+            (Some(_), None) => LockState::Locked,
+            (Some(wc), Some(repos)) if wc.token == repos.token => LockState::Locked,
+            (Some(_), Some(_)) => LockState::Stolen,
+        }
+    }
+}
+
+/// `<wc-status>` element of an entry
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WcStatus {
+    /// status of the item's text
+    #[serde(rename(deserialize = "@item"), deserialize_with = "to_itemtype")]
+    pub item: StatusItemType,
+    /// status of the item's properties, one of none, normal, modified or conflicted
+    #[serde(rename(deserialize = "@props"), deserialize_with = "to_itemtype")]
+    pub props: StatusItemType,
+    /// working revision, absent for unversioned and newly added items
+    #[serde(rename(deserialize = "@revision"))]
+    pub revision: Option<u32>,
+    /// directory is locked by an unfinished svn operation
+    #[serde(rename(deserialize = "@wc-locked"), default)]
+    pub wc_locked: bool,
+    /// item is scheduled for addition with history
+    #[serde(rename(deserialize = "@copied"), default)]
+    pub copied: bool,
+    /// item is switched relative to its parent
+    #[serde(rename(deserialize = "@switched"), default)]
+    pub switched: bool,
+    /// item is the victim of a tree conflict
+    #[serde(rename(deserialize = "@tree-conflicted"), default)]
+    pub tree_conflicted: bool,
+    /// item is a file external
+    #[serde(rename(deserialize = "@file-external"), default)]
+    pub file_external: bool,
+    /// item was moved here from this path
+    #[serde(rename(deserialize = "@moved-from"))]
+    pub moved_from: Option<PathBuf>,
+    /// item was moved away to this path
+    #[serde(rename(deserialize = "@moved-to"))]
+    pub moved_to: Option<PathBuf>,
+    /// last commit of the item, absent for unversioned and newly added items
+    pub commit: Option<EntryCommit>,
+    /// lock token held by this working copy
+    pub lock: Option<EntryLock>,
+}
+
+/// `<repos-status>` element of an entry, the change waiting in the repository
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ReposStatus {
+    /// incoming change of the item, one of none, added, deleted, modified or replaced
+    #[serde(rename(deserialize = "@item"), deserialize_with = "to_itemtype")]
+    pub item: StatusItemType,
+    /// incoming change of the item's properties, none or modified
+    #[serde(rename(deserialize = "@props"), deserialize_with = "to_itemtype")]
+    pub props: StatusItemType,
+    /// lock held in the repository
+    pub lock: Option<EntryLock>,
+}
+
 /// Return value of SvnCmd . status()
 #[derive(Debug, Deserialize)]
 struct StatusParser {
-    target: StatusTarget,
+    #[serde(default)]
+    target: Vec<StatusTargetParser>,
+    #[serde(default)]
+    changelist: Vec<StatusChangelist>,
 }
 
 impl StatusParser {
     pub(crate) fn parse<T: AsRef<str>>(text: T) -> Result<Self, SvnError> {
-        match serde_xml_rs::from_str::<StatusParser>(text.as_ref()) {
+        match quick_xml::de::from_str::<StatusParser>(text.as_ref()) {
             Ok(v) => {
                 trace!("{:?}", v);
                 Ok(v)
@@ -72,22 +329,18 @@ impl StatusParser {
 }
 
 #[derive(Debug, Deserialize)]
-struct StatusTarget {
-    entry: Vec<TargetEntry>,
-}
-
-#[derive(Debug, Deserialize)]
-struct TargetEntry {
-    #[serde(deserialize_with = "to_pathbuf")]
+struct StatusTargetParser {
+    #[serde(rename(deserialize = "@path"), deserialize_with = "to_pathbuf")]
     path: PathBuf,
-    #[serde(rename(deserialize = "wc-status"))]
-    wc_status: WcStatus,
+    #[serde(default)]
+    entry: Vec<StatusEntry>,
+    against: Option<Against>,
 }
 
 #[derive(Debug, Deserialize)]
-struct WcStatus {
-    #[serde(deserialize_with = "to_itemtype")]
-    item: StatusItemType,
+struct Against {
+    #[serde(rename(deserialize = "@revision"))]
+    revision: u32,
 }
 
 fn to_pathbuf<'de, D>(deserializer: D) -> Result<PathBuf, D::Error>
@@ -116,6 +369,121 @@ mod tests {
         println!("{:#?}", de);
     }
 
+    #[test]
+    fn status_all_item_kinds() {
+        let de = SvnStatus::parse(SVN_STATUS_ALL_KINDS).unwrap();
+        assert_eq!(de.targets[0].entries.len(), 9);
+        for item in [
+            StatusItemType::Normal,
+            StatusItemType::Missing,
+            StatusItemType::Deleted,
+            StatusItemType::Replaced,
+            StatusItemType::Conflicted,
+            StatusItemType::Ignored,
+            StatusItemType::Obstructed,
+            StatusItemType::Incomplete,
+        ] {
+            assert_eq!(de.status[&item].len(), 1, "{item:?}");
+        }
+        let replaced = &de.targets[0].entries[3].wc_status;
+        assert_eq!(replaced.item, StatusItemType::Replaced);
+        assert_eq!(replaced.props, StatusItemType::Modified);
+        assert_eq!(replaced.revision, Some(120));
+        assert!(replaced.wc_locked);
+        assert!(replaced.copied);
+        assert!(replaced.switched);
+        assert!(!replaced.tree_conflicted);
+        assert_eq!(
+            replaced.commit,
+            Some(EntryCommit {
+                revision: 118,
+                author: "rajput".to_owned(),
+                date: "2021-08-16T15:02:49.091280Z".to_owned(),
+            })
+        );
+        let conflicted = &de.targets[0].entries[4].wc_status;
+        assert!(conflicted.tree_conflicted);
+        assert!(de.targets[0].entries[8].wc_status.file_external);
+        assert_eq!(
+            de.targets[0].entries[8].wc_status.item,
+            StatusItemType::None
+        );
+        assert_eq!(de.targets[0].entries[5].wc_status.revision, None);
+    }
+
+    #[test]
+    fn status_show_updates() {
+        let de = SvnStatus::parse(SVN_STATUS_SHOW_UPDATES).unwrap();
+        assert_eq!(de.against_revision, Some(324700));
+        let out_of_date: Vec<_> = de.out_of_date().map(|e| e.path.clone()).collect();
+        assert_eq!(
+            out_of_date,
+            vec![
+                PathBuf::from("source/src/Game.cpp"),
+                PathBuf::from("source/assets/reels.png"),
+            ]
+        );
+        assert_eq!(de.targets[0].entries[0].lock_state(), LockState::Unlocked);
+        let game = &de.targets[0].entries[1];
+        assert_eq!(game.wc_status.item, StatusItemType::Modified);
+        assert_eq!(
+            game.repos_status.as_ref().unwrap().item,
+            StatusItemType::Modified
+        );
+        let reels = &de.targets[0].entries[2];
+        assert_eq!(reels.lock_state(), LockState::OtherLocked);
+        assert_eq!(
+            reels
+                .repos_status
+                .as_ref()
+                .unwrap()
+                .lock
+                .as_ref()
+                .unwrap()
+                .owner,
+            "goela"
+        );
+        assert_eq!(de.targets[0].entries[3].lock_state(), LockState::Locked);
+        assert_eq!(de.targets[0].entries[4].lock_state(), LockState::Stolen);
+    }
+
+    #[test]
+    fn status_targets_and_changelists() {
+        let de = SvnStatus::parse(SVN_STATUS_CHANGELISTS).unwrap();
+        let target_paths: Vec<_> = de.targets.iter().map(|t| t.path.clone()).collect();
+        assert_eq!(
+            target_paths,
+            vec![PathBuf::from("."), PathBuf::from("GDK\\GDKBase")]
+        );
+        assert_eq!(de.targets[0].entries.len(), 2);
+        assert_eq!(de.targets[1].entries.len(), 1);
+        assert_eq!(de.changelists.len(), 2);
+        let feature = de.changelist("feature-reels").unwrap();
+        assert_eq!(
+            feature
+                .entries
+                .iter()
+                .map(|e| e.path.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("Games\\src\\Reels.cpp"),
+                PathBuf::from("Games\\src\\Reels.h"),
+            ]
+        );
+        assert!(de.changelist("missing").is_none());
+        let by_changelist = de.by_changelist();
+        assert_eq!(
+            by_changelist.keys().collect::<Vec<_>>(),
+            vec!["feature-reels", "hotfix"]
+        );
+        assert_eq!(
+            by_changelist["hotfix"],
+            vec![PathBuf::from("Games\\src\\Payout.cpp")]
+        );
+        assert_eq!(de.entries().count(), 6);
+        assert_eq!(de.status[&StatusItemType::Modified].len(), 4);
+    }
+
     const SVN_STATUS: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
 <status>
     <target path=".">
@@ -226,4 +594,301 @@ mod tests {
     </target>
 </status>
     "##;
+
+    const SVN_STATUS_ALL_KINDS: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<status>
+<target
+   path=".">
+<entry
+   path="src\main.cpp">
+<wc-status
+   item="normal"
+   props="none"
+   revision="120">
+<commit
+   revision="118">
+<author>rajput</author>
+<date>2021-08-16T15:02:49.091280Z</date>
+</commit>
+</wc-status>
+</entry>
+<entry
+   path="src\gone.cpp">
+<wc-status
+   item="missing"
+   props="none"
+   revision="120">
+</wc-status>
+</entry>
+<entry
+   path="src\old.cpp">
+<wc-status
+   item="deleted"
+   props="none"
+   revision="120">
+</wc-status>
+</entry>
+<entry
+   path="src\swapped.cpp">
+<wc-status
+   item="replaced"
+   props="modified"
+   revision="120"
+   wc-locked="true"
+   copied="true"
+   switched="true">
+<commit
+   revision="118">
+<author>rajput</author>
+<date>2021-08-16T15:02:49.091280Z</date>
+</commit>
+</wc-status>
+</entry>
+<entry
+   path="src\both.cpp">
+<wc-status
+   item="conflicted"
+   props="conflicted"
+   revision="120"
+   tree-conflicted="true">
+</wc-status>
+</entry>
+<entry
+   path="build">
+<wc-status
+   item="ignored"
+   props="none">
+</wc-status>
+</entry>
+<entry
+   path="assets">
+<wc-status
+   item="obstructed"
+   props="none"
+   revision="120">
+</wc-status>
+</entry>
+<entry
+   path="libs">
+<wc-status
+   item="incomplete"
+   props="none"
+   revision="120">
+</wc-status>
+</entry>
+<entry
+   path="config.json">
+<wc-status
+   item="none"
+   props="none"
+   file-external="true">
+</wc-status>
+</entry>
+</target>
+</status>
+"##;
+
+    const SVN_STATUS_SHOW_UPDATES: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<status>
+<target
+   path="source">
+<entry
+   path="source/Makefile.in">
+<wc-status
+   item="normal"
+   props="none"
+   revision="324690">
+<commit
+   revision="298675">
+<author>rajput</author>
+<date>2020-09-16T06:28:02.597638Z</date>
+</commit>
+</wc-status>
+<repos-status
+   item="none"
+   props="none">
+</repos-status>
+</entry>
+<entry
+   path="source/src/Game.cpp">
+<wc-status
+   item="modified"
+   props="none"
+   revision="324690">
+<commit
+   revision="324641">
+<author>rs102580</author>
+<date>2021-08-20T10:46:56.484066Z</date>
+</commit>
+</wc-status>
+<repos-status
+   item="modified"
+   props="none">
+</repos-status>
+</entry>
+<entry
+   path="source/assets/reels.png">
+<wc-status
+   item="normal"
+   props="normal"
+   revision="324690">
+<commit
+   revision="324549">
+<author>goela</author>
+<date>2021-08-19T10:22:54.573528Z</date>
+</commit>
+</wc-status>
+<repos-status
+   item="none"
+   props="modified">
+<lock>
+<token>opaquelocktoken:2bc9a8a6-3f0d-4c4a-9d0b-f0e4c8b2a111</token>
+<owner>goela</owner>
+<comment>reworking reel art</comment>
+<created>2021-08-21T09:12:43.107212Z</created>
+</lock>
+</repos-status>
+</entry>
+<entry
+   path="source/assets/symbols.png">
+<wc-status
+   item="normal"
+   props="normal"
+   revision="324690">
+<commit
+   revision="324549">
+<author>goela</author>
+<date>2021-08-19T10:22:54.573528Z</date>
+</commit>
+<lock>
+<token>opaquelocktoken:8e3f6a1c-1d59-4b7e-a0b4-3c1b7c6d2e22</token>
+<owner>rajput</owner>
+<created>2021-08-21T10:00:00.000000Z</created>
+</lock>
+</wc-status>
+<repos-status
+   item="none"
+   props="none">
+<lock>
+<token>opaquelocktoken:8e3f6a1c-1d59-4b7e-a0b4-3c1b7c6d2e22</token>
+<owner>rajput</owner>
+<created>2021-08-21T10:00:00.000000Z</created>
+</lock>
+</repos-status>
+</entry>
+<entry
+   path="source/assets/paytable.png">
+<wc-status
+   item="normal"
+   props="normal"
+   revision="324690">
+<commit
+   revision="324549">
+<author>goela</author>
+<date>2021-08-19T10:22:54.573528Z</date>
+</commit>
+<lock>
+<token>opaquelocktoken:1f1e1d1c-0000-4b7e-a0b4-3c1b7c6d2e33</token>
+<owner>rajput</owner>
+<created>2021-08-20T10:00:00.000000Z</created>
+</lock>
+</wc-status>
+<repos-status
+   item="none"
+   props="none">
+<lock>
+<token>opaquelocktoken:9a9b9c9d-1111-4b7e-a0b4-3c1b7c6d2e44</token>
+<owner>goela</owner>
+<created>2021-08-21T11:00:00.000000Z</created>
+</lock>
+</repos-status>
+</entry>
+<against
+   revision="324700"/>
+</target>
+</status>
+"##;
+
+    const SVN_STATUS_CHANGELISTS: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<status>
+<target
+   path=".">
+<entry
+   path="GDK\GDKBase">
+<wc-status
+   item="external"
+   props="none">
+</wc-status>
+</entry>
+<entry
+   path="Games\Makefile.in">
+<wc-status
+   item="modified"
+   props="none"
+   revision="156377">
+<commit
+   revision="142706">
+<author>ddunford</author>
+<date>2020-03-02T19:40:25.301780Z</date>
+</commit>
+</wc-status>
+</entry>
+</target>
+<target
+   path="GDK\GDKBase">
+<entry
+   path="GDK\GDKBase\GDKBase.cpp">
+<wc-status
+   item="modified"
+   props="none"
+   revision="156377">
+<commit
+   revision="142706">
+<author>ddunford</author>
+<date>2020-03-02T19:40:25.301780Z</date>
+</commit>
+</wc-status>
+</entry>
+</target>
+<changelist
+   name="feature-reels">
+<entry
+   path="Games\src\Reels.cpp">
+<wc-status
+   item="modified"
+   props="none"
+   revision="156377">
+<commit
+   revision="142706">
+<author>ddunford</author>
+<date>2020-03-02T19:40:25.301780Z</date>
+</commit>
+</wc-status>
+</entry>
+<entry
+   path="Games\src\Reels.h">
+<wc-status
+   item="modified"
+   props="none"
+   revision="156377">
+<commit
+   revision="142706">
+<author>ddunford</author>
+<date>2020-03-02T19:40:25.301780Z</date>
+</commit>
+</wc-status>
+</entry>
+</changelist>
+<changelist
+   name="hotfix">
+<entry
+   path="Games\src\Payout.cpp">
+<wc-status
+   item="added"
+   props="none">
+</wc-status>
+</entry>
+</changelist>
+</status>
+"##;
 }
@@ -46,6 +46,16 @@ impl CmdVersion {
         trace!("svn version out: {:?}", ret);
         Ok(ret)
     }
+
+    /// version of the installed svn client
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// true if the installed svn client is `major.minor` or newer
+    pub fn is_at_least(&self, major: u64, minor: u64) -> bool {
+        self.version >= Version::new(major, minor, 0)
+    }
 }
 
 #[cfg(test)]
@@ -65,6 +75,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn version_gate() {
+        let cmd_ver = CmdVersion {
+            cmd_path: PathBuf::from("svn"),
+            version: Version::new(1, 9, 7),
+            built_rev: RevisionNumber(1_800_392),
+        };
+        assert!(cmd_ver.is_at_least(1, 9));
+        assert!(cmd_ver.is_at_least(1, 8));
+        assert!(!cmd_ver.is_at_least(1, 10));
+        assert!(!cmd_ver.is_at_least(2, 0));
+    }
+
     const VERSION_OUT: &str = r##"svn, version 1.14.1 (r1886195)
    compiled Feb  9 2021, 20:19:00 on x86-microsoft-windows
 
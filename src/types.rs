@@ -1,12 +1,17 @@
 //! this implements svn types
 
-use std::path::PathBuf;
+use std::{fmt::Display, path::PathBuf};
 use url::Url;
 
 pub(crate) trait ToCmdArgs {
     fn to_cmd_args(&self) -> String;
 }
 
+/// like `ToCmdArgs` but keeps every argument separate, so values containing spaces survive
+pub(crate) trait ToCmdArgList {
+    fn to_cmd_arg_list(&self) -> Vec<String>;
+}
+
 /// Credentials
 #[derive(Debug, Clone)]
 pub struct Credentials {
@@ -100,14 +105,68 @@ pub enum PathType {
     Dir,
 }
 
+/// limits the scope of an operation, `--depth ARG`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Depth {
+    /// only the target itself
+    Empty,
+    /// the target and its immediate file children
+    Files,
+    /// the target and its immediate children
+    Immediates,
+    /// the target and all of its descendants
+    Infinity,
+}
+
+impl Depth {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Files => "files",
+            Self::Immediates => "immediates",
+            Self::Infinity => "infinity",
+        }
+    }
+
+    pub(crate) fn parse(depth: &str) -> Option<Self> {
+        match depth {
+            "empty" => Some(Self::Empty),
+            "files" => Some(Self::Files),
+            "immediates" => Some(Self::Immediates),
+            "infinity" => Some(Self::Infinity),
+            _ => None,
+        }
+    }
+}
+
 /// revision
-#[derive(Debug, PartialEq, Eq)]
-#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum RevisionType {
+    /// latest revision in the repository
     Head,
+    /// revision number
     Revision(u64),
 }
 
+impl RevisionType {
+    pub(crate) fn parse(revision: &str) -> Option<Self> {
+        if revision.eq_ignore_ascii_case("HEAD") {
+            Some(Self::Head)
+        } else {
+            revision.parse().ok().map(Self::Revision)
+        }
+    }
+}
+
+impl Display for RevisionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Head => write!(f, "HEAD"),
+            Self::Revision(rev) => write!(f, "{rev}"),
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub enum Target {
     Local(PathBuf),
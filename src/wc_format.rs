@@ -0,0 +1,227 @@
+//! working copy format, read from `.svn/wc.db` or the `.svn/entries` of old working copies
+
+use crate::errors::SvnError;
+use semver::Version;
+use std::{
+    fs::{self, File},
+    io::Read,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// working copy format of a working copy on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WcFormat {
+    /// directory holding the `.svn` dir, the root for svn 1.7+ working copies
+    pub root: PathBuf,
+    /// format number, `PRAGMA user_version` of `wc.db` or the first line of `entries`
+    pub format: u32,
+}
+
+/// can the installed svn work with a working copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WcCompat {
+    /// usable as is, possibly upgraded by svn on the fly
+    Compatible,
+    /// older than the client handles, run `svn upgrade`, E155036
+    NeedsUpgrade,
+    /// written by a newer client, E155021
+    TooNew,
+}
+
+impl WcFormat {
+    /// format of the working copy holding `path`, `None` when no `.svn` dir is found above it
+    pub fn detect(path: &Path) -> Result<Option<Self>, SvnError> {
+        Self::detect_in(&std::env::current_dir()?, path)
+    }
+
+    // `detect` with a relative `path` taken from `base` instead of the cwd
+    fn detect_in(base: &Path, path: &Path) -> Result<Option<Self>, SvnError> {
+        // `ancestors` of a relative path stop at its first component
+        let path = fs::canonicalize(base.join(path))?;
+        let start = if path.is_file() {
+            path.parent()
+        } else {
+            Some(path.as_path())
+        };
+        for dir in start.into_iter().flat_map(Path::ancestors) {
+            let admin = dir.join(".svn");
+            if !admin.is_dir() {
+                continue;
+            }
+            let wc_db = admin.join("wc.db");
+            let format = if wc_db.is_file() {
+                sqlite_user_version(&wc_db)?
+            } else {
+                // svn 1.6 and older, one `.svn` per directory
+                let entries = fs::read_to_string(admin.join("entries"))?;
+                entries
+                    .lines()
+                    .next()
+                    .and_then(|l| l.trim().parse().ok())
+                    .ok_or_else(|| SvnError::Other(format!("no format in '{}'", admin.display())))?
+            };
+            return Ok(Some(Self {
+                root: dir.to_owned(),
+                format,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// svn release which writes this format, `None` for development formats
+    pub fn svn_release(&self) -> Option<&'static str> {
+        match self.format {
+            4 => Some("1.2"),
+            6 => Some("1.3"),
+            8 => Some("1.4"),
+            9 => Some("1.5"),
+            10 => Some("1.6"),
+            29 => Some("1.7"),
+            31 => Some("1.8"),
+            32 => Some("1.15"),
+            _ => None,
+        }
+    }
+
+    /// whether the `client` svn version can work with the working copy
+    pub fn compat(&self, client: &Version) -> WcCompat {
+        let supported = supported_formats(client);
+        if self.format < *supported.start() {
+            WcCompat::NeedsUpgrade
+        } else if self.format > *supported.end() {
+            WcCompat::TooNew
+        } else {
+            WcCompat::Compatible
+        }
+    }
+}
+
+// formats a client opens, 1.8+ upgrade 1.7 working copies on the fly
+fn supported_formats(client: &Version) -> RangeInclusive<u32> {
+    match (client.major, client.minor) {
+        (1, 0..=6) => 4..=10,
+        (1, 7) => 29..=29,
+        (1, 8..=14) => 29..=31,
+        _ => 29..=32,
+    }
+}
+
+// `PRAGMA user_version`, 4 big endian bytes at offset 60 of the sqlite header
+fn sqlite_user_version(path: &Path) -> Result<u32, SvnError> {
+    let mut header = [0u8; 100];
+    File::open(path)?.read_exact(&mut header)?;
+    if !header.starts_with(b"SQLite format 3\0") {
+        return Err(SvnError::Other(format!(
+            "'{}' isn't a sqlite database",
+            path.display()
+        )));
+    }
+    Ok(u32::from_be_bytes([
+        header[60], header[61], header[62], header[63],
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd_wrapper::stderr_error;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("svn-cmd-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join(".svn")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn detect_format() {
+        let wc = scratch_dir("wc18");
+        let mut header = [0u8; 100];
+        header[..16].copy_from_slice(b"SQLite format 3\0");
+        header[60..64].copy_from_slice(&31u32.to_be_bytes());
+        fs::write(wc.join(".svn/wc.db"), header).unwrap();
+        fs::create_dir_all(wc.join("source/lib")).unwrap();
+        fs::write(wc.join("source/lib/main.cpp"), "").unwrap();
+        let format = WcFormat::detect(&wc.join("source/lib/main.cpp"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(format.root, wc);
+        assert_eq!(format.format, 31);
+        assert_eq!(format.svn_release(), Some("1.8"));
+
+        let old = scratch_dir("wc16");
+        fs::write(old.join(".svn/entries"), "10\n\ndir\n324690\n").unwrap();
+        let format = WcFormat::detect(&old).unwrap().unwrap();
+        assert_eq!(format.format, 10);
+        assert_eq!(
+            format.compat(&Version::new(1, 14, 2)),
+            WcCompat::NeedsUpgrade
+        );
+
+        fs::write(
+            wc.join(".svn/wc.db"),
+            "not a database, long enough to hold a header ......................................",
+        )
+        .unwrap();
+        assert!(WcFormat::detect(&wc).is_err());
+        assert!(WcFormat::detect(&wc.join("missing")).is_err());
+        let _ = fs::remove_dir_all(wc);
+        let _ = fs::remove_dir_all(old);
+    }
+
+    #[test]
+    fn detect_relative_path() {
+        let wc = scratch_dir("wc-relative");
+        let mut header = [0u8; 100];
+        header[..16].copy_from_slice(b"SQLite format 3\0");
+        header[60..64].copy_from_slice(&31u32.to_be_bytes());
+        fs::write(wc.join(".svn/wc.db"), header).unwrap();
+        fs::create_dir_all(wc.join("source/lib")).unwrap();
+        // from a nested dir, where walking up `.` alone stops at once
+        let base = wc.join("source/lib");
+        let here = WcFormat::detect_in(&base, Path::new("."));
+        let parent = WcFormat::detect_in(&base, Path::new("../lib"));
+        let here = here.unwrap().unwrap();
+        assert_eq!(here.root, fs::canonicalize(&wc).unwrap());
+        assert_eq!(here.format, 31);
+        assert_eq!(parent.unwrap(), Some(here));
+        let _ = fs::remove_dir_all(wc);
+    }
+
+    #[test]
+    fn client_compat() {
+        let format = |format| WcFormat {
+            root: PathBuf::new(),
+            format,
+        };
+        let client = Version::new(1, 14, 2);
+        assert_eq!(format(29).compat(&client), WcCompat::Compatible);
+        assert_eq!(format(31).compat(&client), WcCompat::Compatible);
+        assert_eq!(format(32).compat(&client), WcCompat::TooNew);
+        assert_eq!(format(9).compat(&client), WcCompat::NeedsUpgrade);
+        assert_eq!(format(31).compat(&Version::new(1, 7, 22)), WcCompat::TooNew);
+        assert_eq!(
+            format(32).compat(&Version::new(1, 15, 0)),
+            WcCompat::Compatible
+        );
+    }
+
+    #[test]
+    fn format_errors() {
+        let too_old = "svn: E155036: Please see the 'svn upgrade' command\nsvn: E155036: The working copy at 'C:\\wc' is too old (format 10) to work with client version '1.14.2 (r1899510)' (expects format 31). You need to upgrade the working copy first.\n";
+        assert!(matches!(
+            stderr_error(too_old.to_owned()),
+            SvnError::WcNeedsUpgrade(_)
+        ));
+        let too_new = "svn: E155021: This client is too old to work with the working copy at 'C:\\wc' (format 32).\n";
+        assert!(matches!(
+            stderr_error(too_new.to_owned()),
+            SvnError::WcTooNew(_)
+        ));
+        assert!(matches!(
+            stderr_error("svn: E155007: 'C:\\tmp' is not a working copy\n".to_owned()),
+            SvnError::Other(_)
+        ));
+    }
+}
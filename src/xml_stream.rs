@@ -0,0 +1,309 @@
+//! incremental parsing of the xml output of long running svn commands
+
+use crate::errors::SvnError;
+use log::trace;
+use quick_xml::{events::Event, Reader, Writer};
+use serde::de::DeserializeOwned;
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    marker::PhantomData,
+};
+
+/// stdout of a running svn command, read chunk by chunk as svn prints it
+pub(crate) struct StdoutReader {
+    next_chunk: Box<dyn FnMut() -> Option<String> + Send>,
+    chunk: Vec<u8>,
+    pos: usize,
+}
+
+impl StdoutReader {
+    pub(crate) fn new(next_chunk: Box<dyn FnMut() -> Option<String> + Send>) -> Self {
+        Self {
+            next_chunk,
+            chunk: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl Read for StdoutReader {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        while self.pos >= self.chunk.len() {
+            match (self.next_chunk)() {
+                Some(chunk) => {
+                    self.chunk = chunk.into_bytes();
+                    self.pos = 0;
+                }
+                None => return Ok(0), // svn has exited or has been cancelled
+            }
+        }
+        let len = out.len().min(self.chunk.len() - self.pos);
+        out[..len].copy_from_slice(&self.chunk[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// iterator which yields one item per `element` as soon as svn has printed it,
+/// only the xml of the current element is kept in memory
+pub struct XmlStream<T> {
+    reader: Reader<Box<dyn BufRead + Send>>,
+    element: &'static str,
+    buf: Vec<u8>,
+    // elements started and not ended yet, the document included
+    open: usize,
+    done: bool,
+    item: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned> XmlStream<T> {
+    pub(crate) fn new<R: Read + Send + 'static>(reader: R, element: &'static str) -> Self {
+        Self::from_buf_read(BufReader::new(reader), element)
+    }
+
+    pub(crate) fn from_buf_read<R: BufRead + Send + 'static>(
+        reader: R,
+        element: &'static str,
+    ) -> Self {
+        let reader: Box<dyn BufRead + Send> = Box::new(reader);
+        Self {
+            reader: Reader::from_reader(reader),
+            element,
+            buf: Vec::new(),
+            open: 0,
+            done: false,
+            item: PhantomData,
+        }
+    }
+
+    // copy the events of the next `element`, nested elements of the same name included
+    fn next_fragment(&mut self) -> Result<Option<Vec<u8>>, SvnError> {
+        let mut writer: Option<Writer<Vec<u8>>> = None;
+        let mut depth = 0usize;
+        loop {
+            self.buf.clear();
+            let event = self.reader.read_event_into(&mut self.buf)?;
+            let (is_element, complete) = match &event {
+                // svn killed or output cut, don't pass it off as a shorter listing
+                Event::Eof if self.open > 0 => {
+                    return Err(SvnError::Other(format!(
+                        "xml output of svn ended with {} element(s) open",
+                        self.open
+                    )))
+                }
+                Event::Eof => return Ok(None),
+                Event::Start(e) => {
+                    self.open += 1;
+                    let is_element = e.name().as_ref() == self.element.as_bytes();
+                    if is_element {
+                        depth += 1;
+                    }
+                    (is_element, false)
+                }
+                Event::Empty(e) => {
+                    let is_element = e.name().as_ref() == self.element.as_bytes();
+                    (is_element, is_element && depth == 0)
+                }
+                Event::End(e) => {
+                    self.open = self.open.saturating_sub(1);
+                    let is_element = e.name().as_ref() == self.element.as_bytes();
+                    if is_element {
+                        depth = depth.saturating_sub(1);
+                    }
+                    (is_element, is_element && depth == 0)
+                }
+                _ => (false, false),
+            };
+            if is_element && writer.is_none() {
+                writer = Some(Writer::new(Vec::new()));
+            }
+            if let Some(w) = writer.as_mut() {
+                w.write_event(event)?;
+            }
+            if complete {
+                return Ok(writer.map(Writer::into_inner));
+            }
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for XmlStream<T> {
+    type Item = Result<T, SvnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let fragment = match self.next_fragment() {
+            Ok(Some(fragment)) => fragment,
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        };
+        let fragment = match String::from_utf8(fragment) {
+            Ok(fragment) => fragment,
+            Err(e) => {
+                return Some(Err(SvnError::FromUtf8Error(
+                    String::from_utf8_lossy(e.as_bytes()).into_owned(),
+                )))
+            }
+        };
+        trace!("{}", fragment);
+        Some(quick_xml::de::from_str::<T>(&fragment).map_err(SvnError::Deserializer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sub_commands::{list::ListEntry, log::LogEntry, status::StatusEntry};
+
+    #[test]
+    fn stream_in_small_chunks() {
+        let mut chunks: Vec<String> = LIST_XML
+            .as_bytes()
+            .chunks(7)
+            .map(|c| String::from_utf8(c.to_vec()).unwrap())
+            .collect();
+        chunks.reverse();
+        let reader = StdoutReader::new(Box::new(move || chunks.pop()));
+        let entries: Vec<ListEntry> = XmlStream::new(reader, "entry")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.clone().unwrap()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "BaoZhuZhaoFu_PurpleCelebration.vcproj",
+                "assets",
+                "Common.cpp"
+            ]
+        );
+        assert_eq!(entries[0].size, Some(20617));
+        assert_eq!(entries[1].commit.revision, 382999);
+    }
+
+    #[test]
+    fn stream_log_and_status() {
+        let log: Vec<LogEntry> = XmlStream::from_buf_read(LOG_XML.as_bytes(), "logentry")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].revision, 324641);
+        assert_eq!(log[1].msg, "Merged r324639 & r324640 from devline");
+
+        let status: Vec<StatusEntry> = XmlStream::from_buf_read(STATUS_XML.as_bytes(), "entry")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(status.len(), 2);
+        assert_eq!(status[1].wc_status.revision, Some(156377));
+    }
+
+    #[test]
+    fn truncated_output_fails() {
+        let truncated = &LIST_XML[..LIST_XML.find("<name>assets").unwrap()];
+        let mut stream: XmlStream<ListEntry> =
+            XmlStream::from_buf_read(truncated.as_bytes(), "entry");
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+
+        // cut between two entries, the document isn't closed either
+        let truncated = &LIST_XML[..LIST_XML.find("<entry\n   kind=\"dir\"").unwrap()];
+        let mut stream: XmlStream<ListEntry> =
+            XmlStream::from_buf_read(truncated.as_bytes(), "entry");
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_err());
+
+        let mut stream: XmlStream<ListEntry> = XmlStream::from_buf_read("".as_bytes(), "entry");
+        assert!(stream.next().is_none());
+    }
+
+    const LIST_XML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<lists>
+<list
+   path="https://svn.ali.global/GDK_games/GDK_games/BLS/CDS/PurpleCelebration/source">
+<entry
+   kind="file">
+<name>BaoZhuZhaoFu_PurpleCelebration.vcproj</name>
+<size>20617</size>
+<commit
+   revision="373439">
+<author>sa102001</author>
+<date>2023-07-21T05:31:45.995541Z</date>
+</commit>
+</entry>
+<entry
+   kind="dir">
+<name>assets</name>
+<commit
+   revision="382999">
+<author>sa102001</author>
+<date>2024-01-23T09:48:16.976029Z</date>
+</commit>
+</entry>
+<external
+   parent_url="https://svn.ali.global/GDK_games/GDK_games/BLS/CDS/PurpleCelebration/source/lib"
+   target="NitroCommon">
+<entry
+   kind="file">
+<name>Common.cpp</name>
+<size>4512</size>
+<commit
+   revision="310568">
+<author>rajput</author>
+<date>2021-02-04T08:51:11.394823Z</date>
+</commit>
+</entry>
+</external>
+</list>
+</lists>"##;
+
+    const LOG_XML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<log>
+<logentry
+   revision="324641">
+<author>rs102580</author>
+<date>2021-08-20T10:46:56.484066Z</date>
+<msg>Creating RC03 tag</msg>
+</logentry>
+<logentry
+   revision="324640">
+<author>rs102580</author>
+<date>2021-08-20T10:42:11.300901Z</date>
+<msg>Merged r324639 &amp; r324640 from devline</msg>
+</logentry>
+</log>"##;
+
+    const STATUS_XML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<status>
+<target
+   path=".">
+<entry
+   path="Games\.vs">
+<wc-status
+   item="unversioned"
+   props="none">
+</wc-status>
+</entry>
+<entry
+   path="GDK\Mercury\src\OpenGL.cpp">
+<wc-status
+   item="modified"
+   revision="156377"
+   props="none">
+<commit
+   revision="142706">
+<author>ddunford</author>
+<date>2020-03-02T19:40:25.301780Z</date>
+</commit>
+</wc-status>
+</entry>
+</target>
+</status>"##;
+}